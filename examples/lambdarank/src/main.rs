@@ -0,0 +1,129 @@
+extern crate lightgbm;
+extern crate serde_json;
+
+use std::fs;
+
+use lightgbm::{rank_groups, Booster, Dataset};
+use serde_json::json;
+
+/// Rows of a LibSVM file in CSR format.
+struct SparseData {
+    indptr: Vec<i64>,
+    indices: Vec<i32>,
+    values: Vec<f64>,
+    labels: Vec<f32>,
+}
+
+fn load_libsvm(file_path: &str) -> SparseData {
+    let mut data = SparseData {
+        indptr: vec![0],
+        indices: Vec::new(),
+        values: Vec::new(),
+        labels: Vec::new(),
+    };
+    for line in fs::read_to_string(file_path).unwrap().lines() {
+        let mut tokens = line.split_whitespace();
+        data.labels
+            .push(tokens.next().unwrap().parse::<f32>().unwrap());
+        for token in tokens {
+            let mut pair = token.split(':');
+            data.indices
+                .push(pair.next().unwrap().parse::<i32>().unwrap());
+            data.values
+                .push(pair.next().unwrap().parse::<f64>().unwrap());
+        }
+        data.indptr.push(data.values.len() as i64);
+    }
+    data
+}
+
+/// The number of documents of every query, one per line.
+fn load_query(file_path: &str) -> Vec<i32> {
+    fs::read_to_string(file_path)
+        .unwrap()
+        .lines()
+        .map(|line| line.trim().parse::<i32>().unwrap())
+        .collect()
+}
+
+/// NDCG@k of a ranking of documents with the given relevance labels.
+fn ndcg_at(ranking: &[usize], labels: &[f32], k: usize) -> f64 {
+    let dcg = |relevance: &mut dyn Iterator<Item = f32>| -> f64 {
+        relevance
+            .take(k)
+            .enumerate()
+            .map(|(pos, rel)| (2_f64.powf(f64::from(rel)) - 1.0) / (pos as f64 + 2.0).log2())
+            .sum()
+    };
+    let mut ideal = labels.to_vec();
+    ideal.sort_by(|a, b| b.partial_cmp(a).unwrap());
+    let ideal_dcg = dcg(&mut ideal.into_iter());
+    if ideal_dcg == 0.0 {
+        return 1.0;
+    }
+    dcg(&mut ranking.iter().map(|idx| labels[*idx])) / ideal_dcg
+}
+
+fn main() -> std::io::Result<()> {
+    let train = load_libsvm("../../lightgbm-sys/lightgbm/examples/lambdarank/rank.train");
+    let train_groups =
+        load_query("../../lightgbm-sys/lightgbm/examples/lambdarank/rank.train.query");
+    let test = load_libsvm("../../lightgbm-sys/lightgbm/examples/lambdarank/rank.test");
+    let test_groups = load_query("../../lightgbm-sys/lightgbm/examples/lambdarank/rank.test.query");
+    let num_col = train
+        .indices
+        .iter()
+        .chain(&test.indices)
+        .max()
+        .map_or(0, |max| *max as usize + 1);
+
+    let mut train_dataset = Dataset::from_csr(
+        &train.indptr,
+        &train.indices,
+        &train.values,
+        num_col,
+        train.labels,
+    )
+    .unwrap();
+    train_dataset.set_groups(&train_groups).unwrap();
+
+    // the parameters of LightGBM's examples/lambdarank/train.conf
+    let params = json! {
+        {
+            "num_iterations": 100,
+            "objective": "lambdarank",
+            "metric": "ndcg",
+            "eval_at": [1, 3, 5],
+            "max_bin": 255,
+            "learning_rate": 0.1,
+            "num_leaves": 31,
+            "min_data_in_leaf": 50,
+            "min_sum_hessian_in_leaf": 5.0
+        }
+    };
+    let booster = Booster::train(train_dataset, &params).unwrap();
+
+    let scores = booster
+        .predict_csr(&test.indptr, &test.indices, &test.values, num_col)
+        .unwrap();
+    let rankings = rank_groups(&scores[0], &test_groups).unwrap();
+
+    let mut start = 0;
+    let mut ndcg = [0.0; 3];
+    for (query, (ranking, size)) in rankings.iter().zip(&test_groups).enumerate() {
+        let labels = &test.labels[start..start + *size as usize];
+        start += *size as usize;
+        for (sum, k) in ndcg.iter_mut().zip(&[1, 3, 5]) {
+            *sum += ndcg_at(ranking, labels, *k);
+        }
+        let ranked_labels = ranking.iter().map(|idx| labels[*idx]).collect::<Vec<_>>();
+        println!(
+            "query {}: relevance in ranked order {:?}",
+            query, ranked_labels
+        );
+    }
+    for (sum, k) in ndcg.iter().zip(&[1, 3, 5]) {
+        println!("ndcg@{}: {:.4}", k, sum / rankings.len() as f64);
+    }
+    Ok(())
+}
@@ -3,10 +3,33 @@ extern crate cmake;
 
 use cmake::Config;
 use std::env;
+use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// Oldest LightGBM release providing all C API functions used by the bindings.
+const MIN_VERSION: (u32, u32) = (4, 2);
+
+fn check_version() {
+    let version = fs::read_to_string("lightgbm/VERSION.txt").unwrap_or_else(|_| {
+        panic!("lightgbm/VERSION.txt not found, run `git submodule update --init --recursive`")
+    });
+    let version = version.trim();
+    let mut parts = version
+        .split('.')
+        .map(|part| part.parse::<u32>().unwrap_or(0));
+    let found = (parts.next().unwrap_or(0), parts.next().unwrap_or(0));
+    if found < MIN_VERSION {
+        panic!(
+            "LightGBM {} is too old, update the lightgbm submodule to {}.{}.0 or newer",
+            version, MIN_VERSION.0, MIN_VERSION.1
+        );
+    }
+}
+
 fn main() {
+    check_version();
+
     let target = env::var("TARGET").unwrap();
     let out_dir = env::var("OUT_DIR").unwrap();
     let lgbm_root = Path::new(&out_dir).join("lightgbm");
@@ -0,0 +1,68 @@
+//! Export of arrow data through the Arrow C data interface.
+
+use std::convert::TryFrom;
+
+use arrow::array::{Array, StructArray};
+use arrow::ffi::{FFI_ArrowArray, FFI_ArrowSchema};
+use arrow::record_batch::RecordBatch;
+use lightgbm_sys;
+
+use crate::{Error, Result};
+
+/// Chunks of arrow data sharing a schema, exported for LightGBM, which only reads them while
+/// they are alive. The exported memory is released on drop.
+pub(crate) struct ArrowChunks {
+    arrays: Vec<FFI_ArrowArray>,
+    schema: FFI_ArrowSchema,
+    num_rows: usize,
+}
+
+impl ArrowChunks {
+    /// Export record batches as chunks of a table, every column being one feature.
+    pub(crate) fn from_batches(batches: &[RecordBatch]) -> Result<Self> {
+        let first = batches
+            .first()
+            .ok_or_else(|| Error::new("at least one record batch is required"))?;
+        if batches.iter().any(|batch| batch.schema() != first.schema()) {
+            return Err(Error::new("all record batches must have the same schema"));
+        }
+        let schema = FFI_ArrowSchema::try_from(first.schema().as_ref())
+            .map_err(|e| Error::new(e.to_string()))?;
+        let arrays = batches
+            .iter()
+            .map(|batch| FFI_ArrowArray::new(&StructArray::from(batch.clone()).into_data()))
+            .collect();
+        Ok(Self {
+            arrays,
+            schema,
+            num_rows: batches.iter().map(RecordBatch::num_rows).sum(),
+        })
+    }
+
+    /// Export a single array, e.g. a label column, as one chunk.
+    pub(crate) fn from_array(array: &dyn Array) -> Result<Self> {
+        let schema =
+            FFI_ArrowSchema::try_from(array.data_type()).map_err(|e| Error::new(e.to_string()))?;
+        Ok(Self {
+            arrays: vec![FFI_ArrowArray::new(&array.to_data())],
+            schema,
+            num_rows: array.len(),
+        })
+    }
+
+    pub(crate) fn num_chunks(&self) -> i64 {
+        self.arrays.len() as i64
+    }
+
+    pub(crate) fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub(crate) fn chunks(&self) -> *const lightgbm_sys::ArrowArray {
+        self.arrays.as_ptr() as *const lightgbm_sys::ArrowArray
+    }
+
+    pub(crate) fn schema(&self) -> *const lightgbm_sys::ArrowSchema {
+        &self.schema as *const FFI_ArrowSchema as *const lightgbm_sys::ArrowSchema
+    }
+}
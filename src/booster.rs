@@ -1,22 +1,66 @@
 use libc::{c_char, c_double, c_longlong, c_void};
 use std;
+use std::borrow::Borrow;
+use std::collections::HashMap;
 use std::convert::TryInto;
 use std::ffi::CString;
+use std::hash::Hash;
+use std::marker::PhantomData;
 
+#[cfg(feature = "arrow")]
+use arrow::record_batch::RecordBatch;
+#[cfg(feature = "ndarray")]
+use ndarray::{Array2, Array3, ArrayView2};
+#[cfg(feature = "dataframe")]
+use polars::prelude::DataFrame;
 use serde_json::Value;
 
 use lightgbm_sys;
 
-use crate::{Dataset, Error, Result};
+#[cfg(feature = "arrow")]
+use crate::arrow_data::ArrowChunks;
+use crate::categorical::encode_columns;
+#[cfg(feature = "dataframe")]
+use crate::dataframe::{self, NullValues};
+use crate::dataset::{c_strings, check_compressed, read_names};
+use crate::early_stopping::{self, EarlyStopping};
+#[cfg(feature = "ndarray")]
+use crate::matrix;
+use crate::params::{params_string, resolve_aliases};
+use crate::training::{CallbackAction, EvalResult};
+use crate::{
+    rank_groups, CategoryEncoder, Column, CustomMetric, Dataset, DenseMatrix, Error, FeatureValue,
+    IndexPtr, IterationRange, Layout, PredictType, PredictionValue, Predictions, Result,
+    SparseFormat, SparseMatrix, TrainOptions,
+};
+
+/// Metric values recorded after every training iteration.
+///
+/// Keyed by the name of the dataset, then by the metric name, e.g. `history["valid"]["auc"][i]`
+/// is the AUC on the validation dataset named `valid` after iteration `i`. Metrics on the
+/// training dataset are stored under `training`.
+pub type EvalHistory = HashMap<String, HashMap<String, Vec<f64>>>;
 
 /// Core model in LightGBM, containing functions for training, evaluating and predicting.
 pub struct Booster {
-    handle: lightgbm_sys::BoosterHandle,
+    pub(crate) handle: lightgbm_sys::BoosterHandle,
+    best_iteration: Option<i32>,
+    // training and validation datasets, which LightGBM refers to until the booster is freed
+    train_set: Option<Dataset>,
+    valid_sets: Vec<(String, Dataset)>,
 }
 
+/// Name of the training dataset in evaluation results.
+const TRAINING_NAME: &str = "training";
+
 impl Booster {
-    fn new(handle: lightgbm_sys::BoosterHandle) -> Self {
-        Booster { handle }
+    fn from_handle(handle: lightgbm_sys::BoosterHandle) -> Self {
+        Booster {
+            handle,
+            best_iteration: None,
+            train_set: None,
+            valid_sets: Vec::new(),
+        }
     }
 
     /// Init from model file.
@@ -30,7 +74,7 @@ impl Booster {
             &mut handle
         ))?;
 
-        Ok(Booster::new(handle))
+        Ok(Booster::from_handle(handle))
     }
 
     /// Init from model string.
@@ -44,7 +88,7 @@ impl Booster {
             &mut handle
         ))?;
 
-        Ok(Booster::new(handle))
+        Ok(Booster::from_handle(handle))
     }
 
     /// Create a new Booster model with given Dataset and parameters.
@@ -72,305 +116,2625 @@ impl Booster {
     /// let bst = Booster::train(dataset, &params).unwrap();
     /// ```
     pub fn train(dataset: Dataset, parameter: &Value) -> Result<Self> {
+        Self::train_with_valid_sets(dataset, Vec::new(), parameter).map(|(booster, _)| booster)
+    }
+
+    /// Create a new Booster model with given Dataset and parameters, evaluating the
+    /// `metric`s on each named validation Dataset after every iteration.
+    ///
+    /// Validation Datasets have to be created with the training Dataset as reference.
+    /// Returns the trained Booster together with the recorded metric values.
+    ///
+    /// Setting `early_stopping_round` stops training once no metric (only the first one in the
+    /// order of the `metric` parameter if `first_metric_only` is set, or the one chosen with
+    /// [`TrainOptions::early_stopping_metric`]) improved by more than `early_stopping_min_delta`
+    /// on any validation Dataset within that many rounds. The best iteration is then used by default
+    /// for prediction and saving, see [`Booster::best_iteration`].
+    ///
+    /// The returned Booster keeps the Datasets, so training can be continued with
+    /// [`Booster::update_one_iter`].
+    ///
+    /// Example
+    /// ```
+    /// extern crate serde_json;
+    /// use lightgbm::{Dataset, Booster};
+    /// use serde_json::json;
+    ///
+    /// let train = Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train").unwrap();
+    /// let valid = Dataset::from_file_with_reference("lightgbm-sys/lightgbm/examples/binary_classification/binary.test", &train).unwrap();
+    /// let params = json!{
+    ///    {
+    ///         "num_iterations": 3,
+    ///         "objective": "binary",
+    ///         "metric": "auc"
+    ///     }
+    /// };
+    /// let (bst, history) = Booster::train_with_valid_sets(train, vec![("valid", valid)], &params).unwrap();
+    /// println!("{:?}", history["valid"]["auc"]);
+    /// ```
+    pub fn train_with_valid_sets(
+        dataset: Dataset,
+        valid_sets: Vec<(&str, Dataset)>,
+        parameter: &Value,
+    ) -> Result<(Self, EvalHistory)> {
+        let options = valid_sets
+            .into_iter()
+            .fold(TrainOptions::new(), |options, (name, valid_set)| {
+                options.valid_set(name, valid_set)
+            });
+        Self::train_with_options(dataset, parameter, options)
+    }
+
+    /// Create a new Booster model with a custom objective function.
+    ///
+    /// Before every iteration `objective` is called with the current raw scores of the training
+    /// data, its labels and its weights (if set), and has to return the gradients and hessians
+    /// of the loss with respect to the raw scores. For multiclass models scores, gradients and
+    /// hessians are laid out class by class, i.e. index `class * num_data + row`.
+    ///
+    /// The `objective` parameter is replaced by `none`, so `predict` returns raw scores.
+    /// Everything else works like [`Booster::train_with_valid_sets`].
+    ///
+    /// Example
+    /// ```
+    /// extern crate serde_json;
+    /// use lightgbm::{Dataset, Booster};
+    /// use serde_json::json;
+    ///
+    /// let train = Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train").unwrap();
+    /// let params = json!{
+    ///    {
+    ///         "num_iterations": 3,
+    ///         "metric": "auc"
+    ///     }
+    /// };
+    /// // binary logloss
+    /// let objective = |scores: &[f64], labels: &[f32], _weights: Option<&[f32]>| {
+    ///     scores
+    ///         .iter()
+    ///         .zip(labels)
+    ///         .map(|(score, label)| {
+    ///             let p = 1.0 / (1.0 + (-score).exp());
+    ///             ((p - *label as f64) as f32, (p * (1.0 - p)) as f32)
+    ///         })
+    ///         .unzip()
+    /// };
+    /// let (bst, _) = Booster::train_with_objective(train, vec![], &params, objective).unwrap();
+    /// ```
+    pub fn train_with_objective<F>(
+        dataset: Dataset,
+        valid_sets: Vec<(&str, Dataset)>,
+        parameter: &Value,
+        objective: F,
+    ) -> Result<(Self, EvalHistory)>
+    where
+        F: FnMut(&[f64], &[f32], Option<&[f32]>) -> (Vec<f32>, Vec<f32>),
+    {
+        let options = valid_sets
+            .into_iter()
+            .fold(TrainOptions::new(), |options, (name, valid_set)| {
+                options.valid_set(name, valid_set)
+            })
+            .objective(objective);
+        Self::train_with_options(dataset, parameter, options)
+    }
+
+    /// Create a new Booster model with given Dataset, parameters and [`TrainOptions`].
+    ///
+    /// This combines validation Datasets, early stopping, a custom objective, custom metrics
+    /// (see [`CustomMetric`]) and callbacks (see [`crate::Callback`]) in one training run.
+    ///
+    /// Example
+    /// ```
+    /// extern crate serde_json;
+    /// use lightgbm::{Booster, CustomMetric, Dataset, TrainOptions};
+    /// use serde_json::json;
+    ///
+    /// let train = Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train").unwrap();
+    /// let valid = Dataset::from_file_with_reference("lightgbm-sys/lightgbm/examples/binary_classification/binary.test", &train).unwrap();
+    /// let params = json!{
+    ///    {
+    ///         "num_iterations": 3,
+    ///         "objective": "binary",
+    ///         "metric": "auc"
+    ///     }
+    /// };
+    /// let accuracy = CustomMetric::new("accuracy", true, |preds, dataset| {
+    ///     let labels = dataset.get_label().unwrap();
+    ///     let correct = preds
+    ///         .iter()
+    ///         .zip(&labels)
+    ///         .filter(|(p, l)| (**p > 0.5) == (**l > 0.5))
+    ///         .count();
+    ///     correct as f64 / labels.len() as f64
+    /// });
+    /// let options = TrainOptions::new().valid_set("valid", valid).metric(accuracy);
+    /// let (bst, history) = Booster::train_with_options(train, &params, options).unwrap();
+    /// println!("{:?}", history["valid"]["accuracy"]);
+    /// ```
+    pub fn train_with_options(
+        dataset: Dataset,
+        parameter: &Value,
+        options: TrainOptions,
+    ) -> Result<(Self, EvalHistory)> {
+        let TrainOptions {
+            valid_sets,
+            mut objective,
+            metrics,
+            mut callbacks,
+            early_stopping_metric,
+        } = options;
+
+        // custom objectives replace the built-in one
+        let mut parameter = resolve_aliases(parameter)?;
+        if objective.is_some() {
+            parameter["objective"] = Value::from("none");
+        }
+        let parameter = &parameter;
+
         // get num_iterations
         let num_iterations: i64 = if parameter["num_iterations"].is_null() {
             100
         } else {
-            parameter["num_iterations"].as_i64().unwrap()
+            parameter["num_iterations"]
+                .as_i64()
+                .ok_or_else(|| Error::new("num_iterations must be an integer"))?
         };
 
-        // exchange params {"x": "y", "z": 1} => "x=y z=1"
-        let params_string = parameter
-            .as_object()
-            .unwrap()
-            .iter()
-            .map(|(k, v)| format!("{}={}", k, v))
-            .collect::<Vec<_>>()
-            .join(" ");
-        let params_cstring = CString::new(params_string).unwrap();
+        // labels and weights only need to be read once for the custom objective
+        let (labels, weights) = if objective.is_some() {
+            (dataset.get_label()?, dataset.get_weights()?)
+        } else {
+            (Vec::new(), None)
+        };
+        let weights = weights.as_deref();
 
-        let mut handle = std::ptr::null_mut();
-        lgbm_call!(lightgbm_sys::LGBM_BoosterCreate(
-            dataset.handle,
-            params_cstring.as_ptr() as *const c_char,
-            &mut handle
-        ))?;
+        let mut booster = Booster::new(dataset, parameter)?;
+        for (name, valid_set) in valid_sets {
+            booster.add_valid_set(&name, valid_set)?;
+        }
+        let mut history = EvalHistory::new();
+        let mut early_stopping = EarlyStopping::from_params(parameter, early_stopping_metric)?;
 
-        let mut is_finished: i32 = 0;
-        for _ in 1..num_iterations {
-            lgbm_call!(lightgbm_sys::LGBM_BoosterUpdateOneIter(
-                handle,
-                &mut is_finished
-            ))?;
+        for iteration in 0..num_iterations as usize {
+            for callback in callbacks.iter_mut() {
+                callback.before_iteration(&mut booster, iteration)?;
+            }
+
+            let is_finished = match objective.as_mut() {
+                Some(objective) => {
+                    let scores = booster.inner_predict(0)?;
+                    let (grad, hess) = objective(&scores, &labels, weights);
+                    booster.update_one_iter_custom(&grad, &hess)?
+                }
+                None => booster.update_one_iter()?,
+            };
+
+            let results = booster.eval_with_metrics(&metrics)?;
+            for result in &results {
+                history
+                    .entry(result.dataset_name.clone())
+                    .or_default()
+                    .entry(result.metric_name.clone())
+                    .or_default()
+                    .push(result.value);
+            }
+
+            let mut stop = false;
+            if let Some(early_stopping) = early_stopping.as_mut() {
+                stop = early_stopping.update(iteration, &results)?;
+            }
+            for callback in callbacks.iter_mut() {
+                if callback.after_iteration(&mut booster, iteration, &results)?
+                    == CallbackAction::Stop
+                {
+                    stop = true;
+                }
+            }
+            if stop || is_finished {
+                break;
+            }
         }
-        Ok(Booster::new(handle))
+        booster.best_iteration = early_stopping.and_then(|es| es.best_iteration());
+        Ok((booster, history))
     }
 
-    /// Predict results for given data.
+    /// Create a new Booster for step-wise training on `dataset`.
     ///
-    /// Input data example
-    /// ```
-    /// let data = vec![vec![1.0, 0.1, 0.2],
-    ///                vec![0.7, 0.4, 0.5],
-    ///                vec![0.1, 0.7, 1.0]];
-    /// ```
+    /// In contrast to [`Booster::train`] no iterations are run. The Booster keeps the Dataset
+    /// for as long as it is trained.
     ///
-    /// Output data example
+    /// Example
     /// ```
-    /// let output = vec![vec![1.0, 0.109, 0.433]];
+    /// extern crate serde_json;
+    /// use lightgbm::{Dataset, Booster};
+    /// use serde_json::json;
+    ///
+    /// let train = Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train").unwrap();
+    /// let valid = Dataset::from_file_with_reference("lightgbm-sys/lightgbm/examples/binary_classification/binary.test", &train).unwrap();
+    /// let params = json!{
+    ///    {
+    ///         "objective": "binary",
+    ///         "metric": "auc"
+    ///     }
+    /// };
+    /// let mut bst = Booster::new(train, &params).unwrap();
+    /// bst.add_valid_set("valid", valid).unwrap();
+    /// for _ in 0..10 {
+    ///     bst.update_one_iter().unwrap();
+    ///     for result in bst.eval().unwrap() {
+    ///         println!("{}: {} = {}", result.dataset_name, result.metric_name, result.value);
+    ///     }
+    /// }
+    /// assert_eq!(bst.current_iteration(), Ok(10));
     /// ```
-    pub fn predict(&self, data: Vec<Vec<f64>>) -> Result<Vec<Vec<f64>>> {
-        let data_length = data.len();
-        let feature_length = data[0].len();
-        let params = CString::new("").unwrap();
-        let mut out_length: c_longlong = 0;
-        let flat_data = data.into_iter().flatten().collect::<Vec<_>>();
-
-        // get num_class
-        let mut num_class = 0;
-        lgbm_call!(lightgbm_sys::LGBM_BoosterGetNumClasses(
-            self.handle,
-            &mut num_class
-        ))?;
-
-        let out_result: Vec<f64> = vec![Default::default(); data_length * num_class as usize];
+    pub fn new(dataset: Dataset, parameter: &Value) -> Result<Self> {
+        if dataset.has_params() {
+            dataset.check_train_params(parameter)?;
+        }
+        let params_cstring = CString::new(params_string(&resolve_aliases(parameter)?)?).unwrap();
 
-        lgbm_call!(lightgbm_sys::LGBM_BoosterPredictForMat(
-            self.handle,
-            flat_data.as_ptr() as *const c_void,
-            lightgbm_sys::C_API_DTYPE_FLOAT64 as i32,
-            data_length as i32,
-            feature_length as i32,
-            1_i32,
-            0_i32,
-            0_i32,
-            -1_i32,
-            params.as_ptr() as *const c_char,
-            &mut out_length,
-            out_result.as_ptr() as *mut c_double
+        let mut handle = std::ptr::null_mut();
+        lgbm_call!(lightgbm_sys::LGBM_BoosterCreate(
+            dataset.handle,
+            params_cstring.as_ptr() as *const c_char,
+            &mut handle
         ))?;
 
-        // reshape for multiclass [1,2,3,4,5,6] -> [[1,2,3], [4,5,6]]  # 3 class
-        let reshaped_output = if num_class > 1 {
-            out_result
-                .chunks(num_class as usize)
-                .map(|x| x.to_vec())
-                .collect()
-        } else {
-            vec![out_result]
-        };
-        Ok(reshaped_output)
+        let mut booster = Booster::from_handle(handle);
+        booster.train_set = Some(dataset);
+        Ok(booster)
     }
 
-    /// Get Feature Num.
-    pub fn num_feature(&self) -> Result<i32> {
-        let mut out_len = 0;
-        lgbm_call!(lightgbm_sys::LGBM_BoosterGetNumFeature(
+    /// Add a named validation Dataset, which has to be created with the training Dataset as
+    /// reference. The name `training` is reserved for the training Dataset.
+    pub fn add_valid_set(&mut self, name: &str, dataset: Dataset) -> Result<()> {
+        if self.train_set.is_none() {
+            return Err(Error::new(
+                "a booster loaded from a model has no training data",
+            ));
+        }
+        if name == TRAINING_NAME || self.valid_sets.iter().any(|(n, _)| n == name) {
+            return Err(Error::new(format!(
+                "validation dataset name {} is already in use",
+                name
+            )));
+        }
+        lgbm_call!(lightgbm_sys::LGBM_BoosterAddValidData(
             self.handle,
-            &mut out_len
+            dataset.handle
         ))?;
-        Ok(out_len)
+        self.valid_sets.push((name.to_string(), dataset));
+        Ok(())
     }
 
-    /// Get Feature Names.
-    pub fn feature_name(&self) -> Result<Vec<String>> {
-        let num_feature = self.num_feature()?;
-        let feature_name_length = 32;
-        let mut num_feature_names = 0;
-        let mut out_buffer_len = 0;
-        let out_strs = (0..num_feature)
-            .map(|_| {
-                CString::new(" ".repeat(feature_name_length))
-                    .unwrap()
-                    .into_raw() as *mut c_char
-            })
-            .collect::<Vec<_>>();
-        lgbm_call!(lightgbm_sys::LGBM_BoosterGetFeatureNames(
+    /// Train one more iteration. Returns `true` if no further splits can be made.
+    pub fn update_one_iter(&mut self) -> Result<bool> {
+        if self.train_set.is_none() {
+            return Err(Error::new(
+                "a booster loaded from a model has no training data",
+            ));
+        }
+        let mut is_finished: i32 = 0;
+        lgbm_call!(lightgbm_sys::LGBM_BoosterUpdateOneIter(
             self.handle,
-            num_feature as i32,
-            &mut num_feature_names,
-            feature_name_length,
-            &mut out_buffer_len,
-            out_strs.as_ptr() as *mut *mut c_char
+            &mut is_finished
         ))?;
-        let output: Vec<String> = out_strs
-            .into_iter()
-            .map(|s| unsafe { CString::from_raw(s).into_string().unwrap() })
-            .collect();
-        Ok(output)
+        Ok(is_finished == 1)
     }
 
-    // Get Feature Importance
-    pub fn feature_importance(&self) -> Result<Vec<f64>> {
-        let num_feature = self.num_feature()?;
-        let out_result: Vec<f64> = vec![Default::default(); num_feature as usize];
-        lgbm_call!(lightgbm_sys::LGBM_BoosterFeatureImportance(
+    /// Train one more iteration with the given gradients and hessians of a custom objective,
+    /// laid out class by class for multiclass. Returns `true` if no further splits can be made.
+    pub fn update_one_iter_custom(&mut self, grad: &[f32], hess: &[f32]) -> Result<bool> {
+        if self.train_set.is_none() {
+            return Err(Error::new(
+                "a booster loaded from a model has no training data",
+            ));
+        }
+        let mut num_predict = 0_i64;
+        lgbm_call!(lightgbm_sys::LGBM_BoosterGetNumPredict(
             self.handle,
             0_i32,
-            0_i32,
-            out_result.as_ptr() as *mut c_double
+            &mut num_predict
         ))?;
-        Ok(out_result)
-    }
+        if grad.len() != num_predict as usize || hess.len() != num_predict as usize {
+            return Err(Error::new(format!(
+                "got {} gradients and {} hessians, expected {}",
+                grad.len(),
+                hess.len(),
+                num_predict
+            )));
+        }
 
-    /// Save model to file.
-    pub fn save_file(&self, filename: &str) -> Result<()> {
-        let filename_str = CString::new(filename).unwrap();
-        lgbm_call!(lightgbm_sys::LGBM_BoosterSaveModel(
+        let mut is_finished: i32 = 0;
+        lgbm_call!(lightgbm_sys::LGBM_BoosterUpdateOneIterCustom(
             self.handle,
-            0_i32,
-            -1_i32,
-            0_i32,
-            filename_str.as_ptr() as *const c_char
+            grad.as_ptr(),
+            hess.as_ptr(),
+            &mut is_finished
         ))?;
+        Ok(is_finished == 1)
+    }
+
+    /// Remove the trees of the last iteration.
+    pub fn rollback_one_iter(&mut self) -> Result<()> {
+        lgbm_call!(lightgbm_sys::LGBM_BoosterRollbackOneIter(self.handle))?;
         Ok(())
     }
 
-    /// Returns the size the model would have if saved using `save_file`, without having to write the file
-    pub fn save_file_size(&self) -> Result<u64> {
-        let mut out_size = 0_i64;
-        lgbm_call!(lightgbm_sys::LGBM_BoosterSaveModelToString(
+    /// Get the number of iterations trained so far.
+    pub fn current_iteration(&self) -> Result<i32> {
+        let mut out_iteration = 0;
+        lgbm_call!(lightgbm_sys::LGBM_BoosterGetCurrentIteration(
             self.handle,
-            0_i32,
-            -1_i32,
-            0_i32,
-            0,
-            &mut out_size as *mut _,
-            std::ptr::null_mut() as *mut i8
+            &mut out_iteration
         ))?;
-        // subtract 1 because the file doesn't contain the final null character
-        (out_size - 1)
-            .try_into()
-            .map_err(|_| Error::new("size negative"))
+        Ok(out_iteration)
     }
 
-    /// Save model to string. This returns the same content that `save_file` writes into a file.
-    pub fn save_string(&self) -> Result<String> {
-        // get nessesary buffer size
-
-        let mut out_size = 0_i64;
-        lgbm_call!(lightgbm_sys::LGBM_BoosterSaveModelToString(
+    /// Change parameters for the following iterations, e.g. the `learning_rate`.
+    pub fn reset_parameter(&mut self, parameter: &Value) -> Result<()> {
+        let params_cstring = CString::new(params_string(&resolve_aliases(parameter)?)?).unwrap();
+        lgbm_call!(lightgbm_sys::LGBM_BoosterResetParameter(
             self.handle,
-            0_i32,
-            -1_i32,
-            0_i32,
-            0,
-            &mut out_size as *mut _,
-            std::ptr::null_mut() as *mut i8
+            params_cstring.as_ptr() as *const c_char
         ))?;
+        Ok(())
+    }
 
-        // write data to buffer and convert
-        let mut buffer = vec![
-            0u8;
-            out_size
-                .try_into()
-                .map_err(|_| Error::new("size negative"))?
-        ];
-        lgbm_call!(lightgbm_sys::LGBM_BoosterSaveModelToString(
-            self.handle,
-            0_i32,
-            -1_i32,
-            0_i32,
-            buffer.len() as c_longlong,
-            &mut out_size as *mut _,
-            buffer.as_mut_ptr() as *mut c_char
-        ))?;
+    /// Evaluate the built-in metrics on the training Dataset (only if
+    /// `is_provide_training_metric` is set) and all validation Datasets.
+    pub fn eval(&self) -> Result<Vec<EvalResult>> {
+        self.eval_with_metrics(&[])
+    }
 
-        if buffer.pop() != Some(0) {
-            // this should never happen, unless lightgbm has a bug
-            panic!("write out of bounds happened in lightgbm call");
-        }
+    fn eval_with_metrics(&self, metrics: &[CustomMetric]) -> Result<Vec<EvalResult>> {
+        let train_set = match &self.train_set {
+            Some(train_set) => train_set,
+            None => return Ok(Vec::new()),
+        };
+        let eval_names = self.eval_names()?;
 
-        let cstring = CString::new(buffer).map_err(|e| Error::new(e.to_string()))?;
-        cstring
-            .into_string()
-            .map_err(|_| Error::new("can't convert model string to unicode"))
+        // data_idx 0 is the training data, validation data starts at 1
+        let datasets = std::iter::once((TRAINING_NAME, train_set)).chain(
+            self.valid_sets
+                .iter()
+                .map(|(name, valid_set)| (name.as_str(), valid_set)),
+        );
+        let mut results = Vec::new();
+        for (idx, (name, data)) in datasets.enumerate() {
+            let builtin = eval_names
+                .iter()
+                .zip(self.eval_at(idx as i32, eval_names.len())?)
+                .map(|(metric, value)| {
+                    EvalResult::new(
+                        name,
+                        metric,
+                        value,
+                        early_stopping::is_higher_better(metric),
+                    )
+                });
+            results.extend(builtin);
+            if !metrics.is_empty() {
+                let preds = self.inner_predict(idx as i32)?;
+                for metric in metrics {
+                    let value = (metric.func)(&preds, data);
+                    results.push(EvalResult::new(
+                        name,
+                        &metric.name,
+                        value,
+                        metric.higher_is_better,
+                    ));
+                }
+            }
+        }
+        Ok(results)
     }
-}
 
-impl Drop for Booster {
-    fn drop(&mut self) {
-        lgbm_call!(lightgbm_sys::LGBM_BoosterFree(self.handle)).unwrap();
+    /// The best iteration found by early stopping, if it was enabled during training.
+    ///
+    /// `predict`, `save_file`, `save_file_size` and `save_string` only use the trees up to
+    /// this iteration.
+    pub fn best_iteration(&self) -> Option<i32> {
+        self.best_iteration
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use serde_json::json;
-    use std::fs;
-    use std::path::Path;
 
-    fn _read_train_file() -> Result<Dataset> {
-        Dataset::from_file(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.train")
+    /// Number of iterations used by default for prediction and saving, -1 means all.
+    fn default_num_iteration(&self) -> i32 {
+        self.best_iteration.unwrap_or(-1)
     }
 
-    fn _train_booster(params: &Value) -> Booster {
-        let dataset = _read_train_file().unwrap();
-        Booster::train(dataset, &params).unwrap()
-    }
+    /// Get the names of the metrics evaluated during training.
+    fn eval_names(&self) -> Result<Vec<String>> {
+        let mut num_eval = 0;
+        lgbm_call!(lightgbm_sys::LGBM_BoosterGetEvalCounts(
+            self.handle,
+            &mut num_eval
+        ))?;
 
-    fn _default_params() -> Value {
-        let params = json! {
-            {
-                "num_iterations": 1,
-                "objective": "binary",
-                "metric": "auc",
-                "data_random_seed": 0
+        // retry with a larger buffer if a name did not fit
+        let mut name_length = 64;
+        loop {
+            let mut buffers = vec![vec![0u8; name_length]; num_eval as usize];
+            let mut out_strs = buffers
+                .iter_mut()
+                .map(|b| b.as_mut_ptr() as *mut c_char)
+                .collect::<Vec<_>>();
+            let mut out_len = 0;
+            let mut out_buffer_len = 0;
+            lgbm_call!(lightgbm_sys::LGBM_BoosterGetEvalNames(
+                self.handle,
+                num_eval,
+                &mut out_len,
+                name_length,
+                &mut out_buffer_len,
+                out_strs.as_mut_ptr()
+            ))?;
+            if out_buffer_len > name_length {
+                name_length = out_buffer_len;
+                continue;
             }
-        };
-        params
+            return buffers
+                .into_iter()
+                .map(|b| {
+                    let end = b.iter().position(|&c| c == 0).unwrap_or(b.len());
+                    String::from_utf8(b[..end].to_vec())
+                        .map_err(|_| Error::new("can't convert metric name to unicode"))
+                })
+                .collect();
+        }
     }
 
-    #[test]
-    fn predict() {
-        let params = json! {
-            {
-                "num_iterations": 10,
-                "objective": "binary",
-                "metric": "auc",
+    /// Get the current predictions for the dataset at `data_idx`, class by class for multiclass.
+    fn inner_predict(&self, data_idx: i32) -> Result<Vec<f64>> {
+        let mut num_predict = 0_i64;
+        lgbm_call!(lightgbm_sys::LGBM_BoosterGetNumPredict(
+            self.handle,
+            data_idx,
+            &mut num_predict
+        ))?;
+        let mut out_len = 0_i64;
+        let mut out_result: Vec<f64> = vec![Default::default(); num_predict as usize];
+        lgbm_call!(lightgbm_sys::LGBM_BoosterGetPredict(
+            self.handle,
+            data_idx,
+            &mut out_len,
+            out_result.as_mut_ptr()
+        ))?;
+        out_result.truncate(out_len as usize);
+        Ok(out_result)
+    }
+
+    /// Get the current metric values on the dataset at `data_idx`.
+    fn eval_at(&self, data_idx: i32, num_eval: usize) -> Result<Vec<f64>> {
+        let mut out_len = 0;
+        let mut out_result: Vec<f64> = vec![Default::default(); num_eval];
+        lgbm_call!(lightgbm_sys::LGBM_BoosterGetEval(
+            self.handle,
+            data_idx,
+            &mut out_len,
+            out_result.as_mut_ptr()
+        ))?;
+        out_result.truncate(out_len as usize);
+        Ok(out_result)
+    }
+
+    /// Predict results for given data.
+    ///
+    /// Input data example
+    /// ```
+    /// let data = vec![vec![1.0, 0.1, 0.2],
+    ///                vec![0.7, 0.4, 0.5],
+    ///                vec![0.1, 0.7, 1.0]];
+    /// ```
+    ///
+    /// Output data example
+    /// ```
+    /// let output = vec![vec![1.0, 0.109, 0.433]];
+    /// ```
+    pub fn predict<T: FeatureValue>(&self, data: Vec<Vec<T>>) -> Result<Vec<Vec<f64>>> {
+        let num_class = self.num_class()?;
+        let predictions = self.predict_with_type(data, PredictType::Normal)?;
+        Ok(reshape_predictions(predictions.into_values(), num_class))
+    }
+
+    /// Predict raw scores, leaf indices or feature contributions (SHAP values) for given data,
+    /// see [`Predictions`] for the shape of the output.
+    ///
+    /// Example
+    /// ```no_run
+    /// use lightgbm::{Booster, PredictType};
+    ///
+    /// let booster = Booster::from_file("path/to/model.txt").unwrap();
+    /// let data = vec![vec![1.0, 0.1, 0.2], vec![0.7, 0.4, 0.5]];
+    /// let contributions = booster.predict_with_type(data, PredictType::Contrib).unwrap();
+    /// // contribution of the second feature to the first class of the first row
+    /// let contribution = contributions.get(0, 0, 1);
+    /// ```
+    pub fn predict_with_type<T: FeatureValue>(
+        &self,
+        data: Vec<Vec<T>>,
+        predict_type: PredictType,
+    ) -> Result<Predictions> {
+        self.predict_with_range(data, predict_type, IterationRange::default())
+    }
+
+    /// Predict for given data using only the iterations in `range`, e.g. the first K trees.
+    ///
+    /// Example
+    /// ```no_run
+    /// use lightgbm::{Booster, IterationRange, PredictType};
+    ///
+    /// let booster = Booster::from_file("path/to/model.txt").unwrap();
+    /// let data = vec![vec![1.0, 0.1, 0.2], vec![0.7, 0.4, 0.5]];
+    /// // raw scores of the trees from iteration 10 on
+    /// let range = IterationRange::new(10, None);
+    /// let scores = booster.predict_with_range(data, PredictType::RawScore, range).unwrap();
+    /// ```
+    pub fn predict_with_range<T: FeatureValue>(
+        &self,
+        data: Vec<Vec<T>>,
+        predict_type: PredictType,
+        range: IterationRange,
+    ) -> Result<Predictions> {
+        let data_length = data.len();
+        let feature_length = data.first().map_or(0, Vec::len);
+        if data.iter().any(|row| row.len() != feature_length) {
+            return Err(Error::new("all rows must have the same number of features"));
+        }
+        let flat_data = data.into_iter().flatten().collect::<Vec<_>>();
+        let matrix = DenseMatrix::new(&flat_data, data_length, feature_length, Layout::RowMajor)?;
+        self.predict_dense(matrix, predict_type, range)
+    }
+
+    /// Predict for a contiguous dense matrix of `f32` or `f64` values, which is passed to
+    /// LightGBM without copying.
+    ///
+    /// Example
+    /// ```no_run
+    /// use lightgbm::{Booster, DenseMatrix, IterationRange, Layout, PredictType};
+    ///
+    /// let booster = Booster::from_file("path/to/model.txt").unwrap();
+    /// let data: Vec<f32> = vec![1.0, 0.1, 0.2, 0.7, 0.4, 0.5];
+    /// let matrix = DenseMatrix::new(&data, 2, 3, Layout::RowMajor).unwrap();
+    /// let predictions = booster
+    ///     .predict_dense(matrix, PredictType::Normal, IterationRange::default())
+    ///     .unwrap();
+    /// ```
+    pub fn predict_dense<T: FeatureValue>(
+        &self,
+        matrix: DenseMatrix<T>,
+        predict_type: PredictType,
+        range: IterationRange,
+    ) -> Result<Predictions> {
+        let (start_iteration, num_iteration) = range.c_args(self.best_iteration);
+        let row_len = self.predict_row_len(predict_type, start_iteration, num_iteration)?;
+        let mut out_result: Vec<f64> = vec![Default::default(); matrix.num_rows() * row_len];
+        self.predict_mat(
+            matrix,
+            predict_type,
+            start_iteration,
+            num_iteration,
+            &mut out_result,
+        )?;
+
+        Ok(Predictions::new(
+            out_result,
+            matrix.num_rows(),
+            self.num_class()? as usize,
+            row_len,
+            predict_type,
+        ))
+    }
+
+    /// Predict for a contiguous dense matrix into `output`, which may hold `f32` or `f64`
+    /// values and has to have the length [`Predictions::values`] would have, see
+    /// [`Booster::predict_dense`].
+    ///
+    /// Example
+    /// ```no_run
+    /// use lightgbm::{Booster, DenseMatrix, IterationRange, Layout, PredictType};
+    ///
+    /// let booster = Booster::from_file("path/to/model.txt").unwrap();
+    /// let data: Vec<f32> = vec![1.0, 0.1, 0.2, 0.7, 0.4, 0.5];
+    /// let matrix = DenseMatrix::new(&data, 2, 3, Layout::RowMajor).unwrap();
+    /// let mut output = vec![0.0_f32; 2];
+    /// booster
+    ///     .predict_dense_into(matrix, PredictType::Normal, IterationRange::default(), &mut output)
+    ///     .unwrap();
+    /// ```
+    pub fn predict_dense_into<T: FeatureValue, O: PredictionValue>(
+        &self,
+        matrix: DenseMatrix<T>,
+        predict_type: PredictType,
+        range: IterationRange,
+        output: &mut [O],
+    ) -> Result<()> {
+        let (start_iteration, num_iteration) = range.c_args(self.best_iteration);
+        let row_len = self.predict_row_len(predict_type, start_iteration, num_iteration)?;
+        if output.len() != matrix.num_rows() * row_len {
+            return Err(Error::new(format!(
+                "output buffer has length {}, but {} values are predicted",
+                output.len(),
+                matrix.num_rows() * row_len
+            )));
+        }
+        O::write_from_f64(output, |out_result| {
+            self.predict_mat(
+                matrix,
+                predict_type,
+                start_iteration,
+                num_iteration,
+                out_result,
+            )
+        })
+    }
+
+    /// Predict for a dense matrix whose columns are named by `feature_names`, which have to
+    /// be the features of the model in the same order, otherwise an error is returned instead
+    /// of silently predicting for the wrong features.
+    ///
+    /// Example
+    /// ```no_run
+    /// use lightgbm::{Booster, DenseMatrix, IterationRange, Layout, PredictType};
+    ///
+    /// let booster = Booster::from_file("path/to/model.txt").unwrap();
+    /// let data = [1.0, 0.1, 0.7, 0.4];
+    /// let matrix = DenseMatrix::new(&data, 2, 2, Layout::RowMajor).unwrap();
+    /// let predictions = booster
+    ///     .predict_dense_named(
+    ///         matrix,
+    ///         &["amount", "age"],
+    ///         PredictType::Normal,
+    ///         IterationRange::default(),
+    ///     )
+    ///     .unwrap();
+    /// ```
+    pub fn predict_dense_named<T: FeatureValue, S: AsRef<str>>(
+        &self,
+        matrix: DenseMatrix<T>,
+        feature_names: &[S],
+        predict_type: PredictType,
+        range: IterationRange,
+    ) -> Result<Predictions> {
+        if feature_names.len() != matrix.num_cols() {
+            return Err(Error::new(format!(
+                "got {} feature names for {} columns",
+                feature_names.len(),
+                matrix.num_cols()
+            )));
+        }
+        self.validate_feature_names(feature_names)?;
+        self.predict_dense(matrix, predict_type, range)
+    }
+
+    /// Predict for rows given as maps from feature names to values, which are aligned to the
+    /// features of the model as described in [`Booster::feature_name`].
+    ///
+    /// Example
+    /// ```no_run
+    /// use std::collections::HashMap;
+    ///
+    /// use lightgbm::{Booster, IterationRange, PredictType};
+    ///
+    /// let booster = Booster::from_file("path/to/model.txt").unwrap();
+    /// let mut row = HashMap::new();
+    /// row.insert("amount", 1.0);
+    /// row.insert("age", 0.1);
+    /// let predictions = booster
+    ///     .predict_named_rows(&[row], PredictType::Normal, IterationRange::default())
+    ///     .unwrap();
+    /// ```
+    pub fn predict_named_rows<K, T>(
+        &self,
+        rows: &[HashMap<K, T>],
+        predict_type: PredictType,
+        range: IterationRange,
+    ) -> Result<Predictions>
+    where
+        K: Borrow<str> + Eq + Hash,
+        T: FeatureValue,
+    {
+        let features = FeatureIndex::new(self)?;
+        let num_feature = features.names.len();
+        let mut data = vec![T::NAN; rows.len() * num_feature];
+        for (row_idx, row) in rows.iter().enumerate() {
+            for (name, value) in row {
+                data[row_idx * num_feature + features.position(name.borrow())?] = *value;
+            }
+        }
+        let matrix = DenseMatrix::new(&data, rows.len(), num_feature, Layout::RowMajor)?;
+        self.predict_dense_named(matrix, &features.names, predict_type, range)
+    }
+
+    /// Predict for named columns of numbers and string categories, which are aligned to the
+    /// features of the model as described in [`Booster::feature_name`] and encoded with the
+    /// `encoder` the training dataset was created with, see [`Dataset::from_columns`]. Unknown
+    /// categories are missing values.
+    ///
+    /// Example
+    /// ```no_run
+    /// use lightgbm::{Booster, CategoryEncoder, Column, IterationRange, PredictType};
+    ///
+    /// let model_string = std::fs::read_to_string("path/to/model.txt").unwrap();
+    /// let booster = Booster::from_string(&model_string).unwrap();
+    /// let encoder = CategoryEncoder::from_model_string(&model_string).unwrap().unwrap();
+    /// let columns = [
+    ///     ("city", Column::Categorical(&["Paris", "Oslo"])),
+    ///     ("amount", Column::Numeric(&[3.0, 12.5])),
+    /// ];
+    /// let predictions = booster
+    ///     .predict_columns(&columns, &encoder, PredictType::Normal, IterationRange::default())
+    ///     .unwrap();
+    /// ```
+    pub fn predict_columns(
+        &self,
+        columns: &[(&str, Column)],
+        encoder: &CategoryEncoder,
+        predict_type: PredictType,
+        range: IterationRange,
+    ) -> Result<Predictions> {
+        let names = columns.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+        let aligned = FeatureIndex::new(self)?
+            .columns(&names)?
+            .into_iter()
+            .map(|idx| columns[idx])
+            .collect::<Vec<_>>();
+        let (values, num_rows, _) = encode_columns(&aligned, encoder)?;
+        let matrix = DenseMatrix::new(&values, num_rows, aligned.len(), Layout::ColumnMajor)?;
+        self.predict_dense(matrix, predict_type, range)
+    }
+
+    /// Rank the rows of every query by the scores of a ranking model, e.g. one trained with
+    /// the `lambdarank` objective. `group_sizes` are the numbers of contiguous rows of the
+    /// queries, see [`Dataset::set_groups`], and the result holds the indices of the rows
+    /// within their query from best to worst, see [`crate::rank_groups`].
+    ///
+    /// Example
+    /// ```no_run
+    /// use lightgbm::{Booster, DenseMatrix, Layout};
+    ///
+    /// let booster = Booster::from_file("path/to/model.txt").unwrap();
+    /// let data = [1.0, 0.1, 0.7, 0.4, 0.9, 0.8, 0.2, 0.2, 0.1, 0.7];
+    /// let matrix = DenseMatrix::new(&data, 5, 2, Layout::RowMajor).unwrap();
+    /// // two queries with three and two candidates
+    /// let rankings = booster.rank(matrix, &[3, 2]).unwrap();
+    /// let best_of_first_query = rankings[0][0];
+    /// ```
+    pub fn rank<T: FeatureValue>(
+        &self,
+        matrix: DenseMatrix<T>,
+        group_sizes: &[i32],
+    ) -> Result<Vec<Vec<usize>>> {
+        if self.num_class()? != 1 {
+            return Err(Error::new("ranking requires a model with a single output"));
+        }
+        let scores =
+            self.predict_dense(matrix, PredictType::RawScore, IterationRange::default())?;
+        rank_groups(scores.values(), group_sizes)
+    }
+
+    /// Predict for an ndarray matrix of `f32` or `f64` values, passed to LightGBM without
+    /// copying if it is standard or Fortran contiguous.
+    ///
+    /// Returns one row of `predict_type` values per input row, see [`Predictions`], e.g. one
+    /// column per class for [`PredictType::Normal`].
+    ///
+    /// Note: the feature ```ndarray``` is required for this method
+    ///
+    /// Example
+    ///
+    #[cfg_attr(
+        feature = "ndarray",
+        doc = r##"
+```no_run
+extern crate ndarray;
+
+use lightgbm::{Booster, PredictType};
+use ndarray::array;
+
+let booster = Booster::from_file("path/to/model.txt").unwrap();
+let data = array![[1.0, 0.1, 0.2], [0.7, 0.4, 0.5]];
+let predictions = booster.predict_array(data.view(), PredictType::Normal).unwrap();
+```
+"##
+    )]
+    #[cfg(feature = "ndarray")]
+    pub fn predict_array<T: FeatureValue>(
+        &self,
+        data: ArrayView2<T>,
+        predict_type: PredictType,
+    ) -> Result<Array2<f64>> {
+        let predictions = matrix::with_array_view(data, |matrix| {
+            self.predict_dense(matrix, predict_type, IterationRange::default())
+        })?;
+        let (num_rows, dim1, dim2) = predictions.shape();
+        Ok(Array2::from_shape_vec((num_rows, dim1 * dim2), predictions.into_values()).unwrap())
+    }
+
+    /// Predict feature contributions (SHAP values) for an ndarray matrix, with shape
+    /// `rows × classes × (features + 1)`, see [`Booster::predict_array`].
+    ///
+    /// Note: the feature ```ndarray``` is required for this method
+    #[cfg(feature = "ndarray")]
+    pub fn predict_contrib_array<T: FeatureValue>(
+        &self,
+        data: ArrayView2<T>,
+    ) -> Result<Array3<f64>> {
+        let predictions = matrix::with_array_view(data, |matrix| {
+            self.predict_dense(matrix, PredictType::Contrib, IterationRange::default())
+        })?;
+        Ok(Array3::from_shape_vec(predictions.shape(), predictions.into_values()).unwrap())
+    }
+
+    /// Predict for arrow record batches, see [`Dataset::from_arrow`].
+    ///
+    /// Note: the feature ```arrow``` is required for this method
+    ///
+    /// Example
+    ///
+    #[cfg_attr(
+        feature = "arrow",
+        doc = r##"
+```no_run
+extern crate arrow;
+
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, Float64Array};
+use arrow::record_batch::RecordBatch;
+use lightgbm::{Booster, IterationRange, PredictType};
+
+let booster = Booster::from_file("path/to/model.txt").unwrap();
+let batch = RecordBatch::try_from_iter(vec![
+    ("feature_1", Arc::new(Float64Array::from(vec![1.0, 0.7])) as ArrayRef),
+    ("feature_2", Arc::new(Float64Array::from(vec![0.1, 0.4])) as ArrayRef),
+])
+.unwrap();
+let predictions = booster
+    .predict_arrow(&[batch], PredictType::Normal, IterationRange::default())
+    .unwrap();
+```
+"##
+    )]
+    #[cfg(feature = "arrow")]
+    pub fn predict_arrow(
+        &self,
+        batches: &[RecordBatch],
+        predict_type: PredictType,
+        range: IterationRange,
+    ) -> Result<Predictions> {
+        let chunks = ArrowChunks::from_batches(batches)?;
+        let params = CString::new("").unwrap();
+        let (start_iteration, num_iteration) = range.c_args(self.best_iteration);
+        let row_len = self.predict_row_len(predict_type, start_iteration, num_iteration)?;
+        let mut out_length: c_longlong = 0;
+        let mut out_result: Vec<f64> = vec![Default::default(); chunks.num_rows() * row_len];
+
+        lgbm_call!(lightgbm_sys::LGBM_BoosterPredictForArrow(
+            self.handle,
+            chunks.num_chunks(),
+            chunks.chunks(),
+            chunks.schema(),
+            predict_type.as_c_int(),
+            start_iteration,
+            num_iteration,
+            params.as_ptr() as *const c_char,
+            &mut out_length,
+            out_result.as_mut_ptr()
+        ))?;
+
+        Ok(Predictions::new(
+            out_result,
+            chunks.num_rows(),
+            self.num_class()? as usize,
+            row_len,
+            predict_type,
+        ))
+    }
+
+    /// Predict for a polars DataFrame, whose columns are aligned to the features of the model
+    /// as described in [`Booster::feature_name`]. Nulls are missing values.
+    ///
+    /// Categorical, enum and string columns are encoded with the `encoder` the training
+    /// dataset was created with, see [`Dataset::from_dataframe_with_options`]. Unknown
+    /// categories are missing values.
+    ///
+    /// Note: the feature ```dataframe``` is required for this method
+    ///
+    /// Example
+    ///
+    #[cfg_attr(
+        feature = "dataframe",
+        doc = r##"
+```no_run
+extern crate polars;
+
+use lightgbm::{Booster, CategoryEncoder, IterationRange, PredictType};
+use polars::df;
+use polars::prelude::*;
+
+let model_string = std::fs::read_to_string("path/to/model.txt").unwrap();
+let booster = Booster::from_string(&model_string).unwrap();
+let encoder = CategoryEncoder::from_model_string(&model_string).unwrap().unwrap();
+let df: DataFrame = df![
+    "country" => ["DE", "FR"],
+    "amount" => [1.0, 0.7],
+]
+.unwrap();
+let predictions = booster
+    .predict_dataframe(&df, &encoder, PredictType::Normal, IterationRange::default())
+    .unwrap();
+```
+"##
+    )]
+    #[cfg(feature = "dataframe")]
+    pub fn predict_dataframe(
+        &self,
+        dataframe: &DataFrame,
+        encoder: &CategoryEncoder,
+        predict_type: PredictType,
+        range: IterationRange,
+    ) -> Result<Predictions> {
+        let features = FeatureIndex::new(self)?;
+        features.columns(&dataframe.get_column_names_str())?;
+        let names = features
+            .names
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        let features = dataframe::feature_columns(dataframe, &names, NullValues::Missing, encoder)?;
+        let matrix = DenseMatrix::new(
+            &features.values,
+            features.num_rows,
+            names.len(),
+            Layout::ColumnMajor,
+        )?;
+        self.predict_dense(matrix, predict_type, range)
+    }
+
+    fn predict_mat<T: FeatureValue>(
+        &self,
+        matrix: DenseMatrix<T>,
+        predict_type: PredictType,
+        start_iteration: i32,
+        num_iteration: i32,
+        out_result: &mut [f64],
+    ) -> Result<()> {
+        let params = CString::new("").unwrap();
+        let mut out_length: c_longlong = 0;
+
+        lgbm_call!(lightgbm_sys::LGBM_BoosterPredictForMat(
+            self.handle,
+            matrix.data().as_ptr() as *const c_void,
+            T::DTYPE,
+            matrix.num_rows() as i32,
+            matrix.num_cols() as i32,
+            matrix.is_row_major(),
+            predict_type.as_c_int(),
+            start_iteration,
+            num_iteration,
+            params.as_ptr() as *const c_char,
+            &mut out_length,
+            out_result.as_mut_ptr()
+        ))?;
+        Ok(())
+    }
+
+    /// Predict results for a sparse matrix in CSR format, see [`Dataset::from_csr`].
+    ///
+    /// The output has the same layout as the one of [`Booster::predict`].
+    ///
+    /// Example
+    /// ```no_run
+    /// use lightgbm::Booster;
+    ///
+    /// let booster = Booster::from_file("path/to/model.txt").unwrap();
+    /// // [[1.0, 0.0, 0.0],
+    /// //  [0.0, 0.0, 2.0]]
+    /// let indptr: Vec<i32> = vec![0, 1, 2];
+    /// let values: Vec<f64> = vec![1.0, 2.0];
+    /// let result = booster.predict_csr(&indptr, &[0, 2], &values, 3).unwrap();
+    /// ```
+    pub fn predict_csr<P: IndexPtr, T: FeatureValue>(
+        &self,
+        indptr: &[P],
+        indices: &[i32],
+        values: &[T],
+        num_col: usize,
+    ) -> Result<Vec<Vec<f64>>> {
+        check_compressed(indptr, indices, values, num_col)?;
+        let params = CString::new("").unwrap();
+        let num_class = self.num_class()?;
+        let num_row = indptr.len() - 1;
+        let mut out_length: c_longlong = 0;
+        let mut out_result: Vec<f64> = vec![Default::default(); num_row * num_class as usize];
+
+        lgbm_call!(lightgbm_sys::LGBM_BoosterPredictForCSR(
+            self.handle,
+            indptr.as_ptr() as *const c_void,
+            P::DTYPE,
+            indices.as_ptr(),
+            values.as_ptr() as *const c_void,
+            T::DTYPE,
+            indptr.len() as i64,
+            values.len() as i64,
+            num_col as i64,
+            0_i32,
+            0_i32,
+            self.default_num_iteration(),
+            params.as_ptr() as *const c_char,
+            &mut out_length,
+            out_result.as_mut_ptr()
+        ))?;
+
+        Ok(reshape_predictions(out_result, num_class))
+    }
+
+    /// Predict results for a sparse matrix in CSC format, see [`Dataset::from_csc`].
+    ///
+    /// The output has the same layout as the one of [`Booster::predict`].
+    pub fn predict_csc<P: IndexPtr, T: FeatureValue>(
+        &self,
+        col_ptr: &[P],
+        row_indices: &[i32],
+        values: &[T],
+        num_row: usize,
+    ) -> Result<Vec<Vec<f64>>> {
+        check_compressed(col_ptr, row_indices, values, num_row)?;
+        let params = CString::new("").unwrap();
+        let num_class = self.num_class()?;
+        let mut out_length: c_longlong = 0;
+        let mut out_result: Vec<f64> = vec![Default::default(); num_row * num_class as usize];
+
+        lgbm_call!(lightgbm_sys::LGBM_BoosterPredictForCSC(
+            self.handle,
+            col_ptr.as_ptr() as *const c_void,
+            P::DTYPE,
+            row_indices.as_ptr(),
+            values.as_ptr() as *const c_void,
+            T::DTYPE,
+            col_ptr.len() as i64,
+            values.len() as i64,
+            num_row as i64,
+            0_i32,
+            0_i32,
+            self.default_num_iteration(),
+            params.as_ptr() as *const c_char,
+            &mut out_length,
+            out_result.as_mut_ptr()
+        ))?;
+
+        Ok(reshape_predictions(out_result, num_class))
+    }
+
+    /// Predict the result for a single sparse row, given by the column `indices` of its
+    /// non-zero `values`.
+    ///
+    /// Returns one value per class.
+    ///
+    /// Example
+    /// ```no_run
+    /// use lightgbm::Booster;
+    ///
+    /// let booster = Booster::from_file("path/to/model.txt").unwrap();
+    /// // [0.0, 1.5, 0.0, 0.3]
+    /// let result = booster.predict_csr_single_row(&[1, 3], &[1.5, 0.3], 4).unwrap();
+    /// ```
+    pub fn predict_csr_single_row<T: FeatureValue>(
+        &self,
+        indices: &[i32],
+        values: &[T],
+        num_col: usize,
+    ) -> Result<Vec<f64>> {
+        let indptr = [0, values.len() as i64];
+        check_compressed(&indptr, indices, values, num_col)?;
+        let params = CString::new("").unwrap();
+        let mut out_length: c_longlong = 0;
+        let mut out_result: Vec<f64> = vec![Default::default(); self.num_class()? as usize];
+
+        lgbm_call!(lightgbm_sys::LGBM_BoosterPredictForCSRSingleRow(
+            self.handle,
+            indptr.as_ptr() as *const c_void,
+            i64::DTYPE,
+            indices.as_ptr(),
+            values.as_ptr() as *const c_void,
+            T::DTYPE,
+            indptr.len() as i64,
+            values.len() as i64,
+            num_col as i64,
+            0_i32,
+            0_i32,
+            self.default_num_iteration(),
+            params.as_ptr() as *const c_char,
+            &mut out_length,
+            out_result.as_mut_ptr()
+        ))?;
+
+        Ok(out_result)
+    }
+
+    /// Predict feature contributions (SHAP values) for a sparse matrix in CSR format, see
+    /// [`Dataset::from_csr`], without ever materializing them as a dense matrix.
+    ///
+    /// Returns one `rows × (features + 1)` matrix per class, the last column holds the
+    /// expected value of the model.
+    ///
+    /// Example
+    /// ```no_run
+    /// use lightgbm::Booster;
+    ///
+    /// let booster = Booster::from_file("path/to/model.txt").unwrap();
+    /// let indptr: Vec<i32> = vec![0, 1, 2];
+    /// let values: Vec<f64> = vec![1.0, 2.0];
+    /// let contributions = booster.predict_contrib_csr(&indptr, &[0, 2], &values, 3).unwrap();
+    /// // contribution of the third feature to the second row
+    /// let contribution = contributions[0].get(1, 2).unwrap_or(0.0);
+    /// ```
+    pub fn predict_contrib_csr<P: IndexPtr, T: FeatureValue>(
+        &self,
+        indptr: &[P],
+        indices: &[i32],
+        values: &[T],
+        num_col: usize,
+    ) -> Result<Vec<SparseMatrix<T>>> {
+        check_compressed(indptr, indices, values, num_col)?;
+        self.predict_sparse_output(indptr, indices, values, num_col, SparseFormat::Csr)
+    }
+
+    /// Predict feature contributions (SHAP values) for a sparse matrix in CSC format, see
+    /// [`Dataset::from_csc`] and [`Booster::predict_contrib_csr`].
+    pub fn predict_contrib_csc<P: IndexPtr, T: FeatureValue>(
+        &self,
+        col_ptr: &[P],
+        row_indices: &[i32],
+        values: &[T],
+        num_row: usize,
+    ) -> Result<Vec<SparseMatrix<T>>> {
+        check_compressed(col_ptr, row_indices, values, num_row)?;
+        self.predict_sparse_output(col_ptr, row_indices, values, num_row, SparseFormat::Csc)
+    }
+
+    fn predict_sparse_output<P: IndexPtr, T: FeatureValue>(
+        &self,
+        indptr: &[P],
+        indices: &[i32],
+        values: &[T],
+        inner_len: usize,
+        format: SparseFormat,
+    ) -> Result<Vec<SparseMatrix<T>>> {
+        let params = CString::new("").unwrap();
+        let matrix_type = match format {
+            SparseFormat::Csr => lightgbm_sys::C_API_MATRIX_TYPE_CSR,
+            SparseFormat::Csc => lightgbm_sys::C_API_MATRIX_TYPE_CSC,
+        };
+        let mut out_len = [0_i64; 2];
+        let mut out = SparseOutput::<P, T> {
+            indptr: std::ptr::null_mut(),
+            indices: std::ptr::null_mut(),
+            data: std::ptr::null_mut(),
+            _types: PhantomData,
+        };
+
+        lgbm_call!(lightgbm_sys::LGBM_BoosterPredictSparseOutput(
+            self.handle,
+            indptr.as_ptr() as *const c_void,
+            P::DTYPE,
+            indices.as_ptr(),
+            values.as_ptr() as *const c_void,
+            T::DTYPE,
+            indptr.len() as i64,
+            values.len() as i64,
+            inner_len as i64,
+            PredictType::Contrib.as_c_int(),
+            0_i32,
+            self.default_num_iteration(),
+            params.as_ptr() as *const c_char,
+            matrix_type as i32,
+            out_len.as_mut_ptr(),
+            &mut out.indptr,
+            &mut out.indices,
+            &mut out.data
+        ))?;
+        if out.indptr.is_null() || out.indices.is_null() || out.data.is_null() {
+            return Err(Error::new("LightGBM returned no contributions"));
+        }
+
+        // the outputs of all classes are concatenated, every class has its own index pointer
+        // (one entry longer for CSC, due to the additional expected value column) with offsets
+        // relative to the start of its values
+        let (num_row, num_col, class_indptr_len) = match format {
+            SparseFormat::Csr => (indptr.len() - 1, inner_len + 1, indptr.len()),
+            SparseFormat::Csc => (inner_len, indptr.len(), indptr.len() + 1),
+        };
+        let (out_indptr, out_indices, out_data) = unsafe {
+            (
+                std::slice::from_raw_parts(out.indptr as *const P, out_len[1] as usize),
+                std::slice::from_raw_parts(out.indices as *const i32, out_len[0] as usize),
+                std::slice::from_raw_parts(out.data as *const T, out_len[0] as usize),
+            )
+        };
+        let mut matrices = Vec::new();
+        let mut offset = 0;
+        for class_indptr in out_indptr.chunks(class_indptr_len) {
+            let class_indptr = class_indptr
+                .iter()
+                .map(|p| (*p).into())
+                .collect::<Vec<i64>>();
+            let start = class_indptr[0];
+            let end = class_indptr[class_indptr.len() - 1];
+            let range = (offset + start) as usize..(offset + end) as usize;
+            if range.end > out_indices.len() {
+                return Err(Error::new("LightGBM returned malformed contributions"));
+            }
+            matrices.push(SparseMatrix::new(
+                format,
+                (num_row, num_col),
+                class_indptr.iter().map(|p| p - start).collect(),
+                out_indices[range.clone()].to_vec(),
+                out_data[range].to_vec(),
+            ));
+            offset += end;
+        }
+        Ok(matrices)
+    }
+
+    /// Get the number of values predicted per row.
+    pub(crate) fn predict_row_len(
+        &self,
+        predict_type: PredictType,
+        start_iteration: i32,
+        num_iteration: i32,
+    ) -> Result<usize> {
+        let mut out_len = 0;
+        lgbm_call!(lightgbm_sys::LGBM_BoosterCalcNumPredict(
+            self.handle,
+            1,
+            predict_type.as_c_int(),
+            start_iteration,
+            num_iteration,
+            &mut out_len
+        ))?;
+        Ok(out_len as usize)
+    }
+
+    /// Get the number of classes, 1 unless the model is multiclass.
+    fn num_class(&self) -> Result<i32> {
+        let mut num_class = 0;
+        lgbm_call!(lightgbm_sys::LGBM_BoosterGetNumClasses(
+            self.handle,
+            &mut num_class
+        ))?;
+        Ok(num_class)
+    }
+
+    /// Get Feature Num.
+    pub fn num_feature(&self) -> Result<i32> {
+        let mut out_len = 0;
+        lgbm_call!(lightgbm_sys::LGBM_BoosterGetNumFeature(
+            self.handle,
+            &mut out_len
+        ))?;
+        Ok(out_len)
+    }
+
+    /// Get Feature Names.
+    ///
+    /// Named input of [`Booster::predict_named_rows`], [`Booster::predict_columns`] and
+    /// [`Booster::predict_dataframe`] is aligned to these names: names which are not features
+    /// of the model are rejected, as they are most likely misspelled. A feature missing from a
+    /// row is a missing value, a feature missing from the columns is an error.
+    pub fn feature_name(&self) -> Result<Vec<String>> {
+        let num_feature = self.num_feature()?;
+        read_names(
+            num_feature as usize,
+            |buffer_len, out_buffer_len, out_strs| {
+                let mut num_feature_names = 0;
+                lgbm_call!(lightgbm_sys::LGBM_BoosterGetFeatureNames(
+                    self.handle,
+                    num_feature,
+                    &mut num_feature_names,
+                    buffer_len,
+                    out_buffer_len,
+                    out_strs
+                ))
+            },
+        )
+    }
+
+    /// Check that `feature_names` are the names of the features of the model, in the same
+    /// order, e.g. the columns of data to predict for.
+    ///
+    /// Example
+    /// ```no_run
+    /// use lightgbm::Booster;
+    ///
+    /// let booster = Booster::from_file("path/to/model.txt").unwrap();
+    /// if booster.validate_feature_names(&["amount", "age"]).is_err() {
+    ///     println!("the model expects {:?}", booster.feature_name().unwrap());
+    /// }
+    /// ```
+    pub fn validate_feature_names<S: AsRef<str>>(&self, feature_names: &[S]) -> Result<()> {
+        let names = c_strings(feature_names)?;
+        let mut name_ptrs = names
+            .iter()
+            .map(|name| name.as_ptr())
+            .collect::<Vec<*const c_char>>();
+        lgbm_call!(lightgbm_sys::LGBM_BoosterValidateFeatureNames(
+            self.handle,
+            name_ptrs.as_mut_ptr(),
+            name_ptrs.len() as i32
+        ))?;
+        Ok(())
+    }
+
+    // Get Feature Importance
+    pub fn feature_importance(&self) -> Result<Vec<f64>> {
+        let num_feature = self.num_feature()?;
+        let out_result: Vec<f64> = vec![Default::default(); num_feature as usize];
+        lgbm_call!(lightgbm_sys::LGBM_BoosterFeatureImportance(
+            self.handle,
+            0_i32,
+            0_i32,
+            out_result.as_ptr() as *mut c_double
+        ))?;
+        Ok(out_result)
+    }
+
+    /// Save model to file.
+    pub fn save_file(&self, filename: &str) -> Result<()> {
+        self.save_file_with_range(filename, IterationRange::default())
+    }
+
+    /// Save the iterations in `range` of the model to file.
+    pub fn save_file_with_range(&self, filename: &str, range: IterationRange) -> Result<()> {
+        let filename_str = CString::new(filename).unwrap();
+        let (start_iteration, num_iteration) = range.c_args(self.best_iteration);
+        lgbm_call!(lightgbm_sys::LGBM_BoosterSaveModel(
+            self.handle,
+            start_iteration,
+            num_iteration,
+            0_i32,
+            filename_str.as_ptr() as *const c_char
+        ))?;
+        Ok(())
+    }
+
+    /// Returns the size the model would have if saved using `save_file`, without having to write the file
+    pub fn save_file_size(&self) -> Result<u64> {
+        self.save_file_size_with_range(IterationRange::default())
+    }
+
+    /// Returns the size the model would have if saved using `save_file_with_range`.
+    pub fn save_file_size_with_range(&self, range: IterationRange) -> Result<u64> {
+        let mut out_size = 0_i64;
+        let (start_iteration, num_iteration) = range.c_args(self.best_iteration);
+        lgbm_call!(lightgbm_sys::LGBM_BoosterSaveModelToString(
+            self.handle,
+            start_iteration,
+            num_iteration,
+            0_i32,
+            0,
+            &mut out_size as *mut _,
+            std::ptr::null_mut() as *mut i8
+        ))?;
+        // subtract 1 because the file doesn't contain the final null character
+        (out_size - 1)
+            .try_into()
+            .map_err(|_| Error::new("size negative"))
+    }
+
+    /// Save model to string. This returns the same content that `save_file` writes into a file.
+    pub fn save_string(&self) -> Result<String> {
+        self.save_string_with_range(IterationRange::default())
+    }
+
+    /// Save the model to string together with `encoder`, which can be read back with
+    /// [`CategoryEncoder::from_model_string`]. LightGBM ignores the encoder when loading the
+    /// model, so the string can be loaded like any other model.
+    ///
+    /// Example
+    /// ```
+    /// extern crate serde_json;
+    /// use lightgbm::{Booster, CategoryEncoder, Column, Dataset, DatasetParams};
+    /// use serde_json::json;
+    ///
+    /// let mut encoder = CategoryEncoder::new();
+    /// let columns = [
+    ///     ("amount", Column::Numeric(&[10.0, 7.5, 2.0, 1.0])),
+    ///     ("city", Column::Categorical(&["Berlin", "Paris", "Berlin", "Rome"])),
+    /// ];
+    /// let label = [0.0, 1.0, 0.0, 1.0];
+    /// let params = DatasetParams::default();
+    /// let dataset = Dataset::from_columns(&columns, &label, &mut encoder, &params).unwrap();
+    /// let bst = Booster::train(dataset, &json!{{"num_iterations": 3, "objective": "binary"}}).unwrap();
+    ///
+    /// let model_string = bst.save_string_with_encoder(&encoder).unwrap();
+    /// let bst = Booster::from_string(&model_string).unwrap();
+    /// let encoder = CategoryEncoder::from_model_string(&model_string).unwrap().unwrap();
+    /// ```
+    pub fn save_string_with_encoder(&self, encoder: &CategoryEncoder) -> Result<String> {
+        let mut model_string = self.save_string()?;
+        encoder.append_to_model_string(&mut model_string);
+        Ok(model_string)
+    }
+
+    /// Save the iterations in `range` of the model to string.
+    pub fn save_string_with_range(&self, range: IterationRange) -> Result<String> {
+        let (start_iteration, num_iteration) = range.c_args(self.best_iteration);
+        // get nessesary buffer size
+
+        let mut out_size = 0_i64;
+        lgbm_call!(lightgbm_sys::LGBM_BoosterSaveModelToString(
+            self.handle,
+            start_iteration,
+            num_iteration,
+            0_i32,
+            0,
+            &mut out_size as *mut _,
+            std::ptr::null_mut() as *mut i8
+        ))?;
+
+        // write data to buffer and convert
+        let mut buffer = vec![
+            0u8;
+            out_size
+                .try_into()
+                .map_err(|_| Error::new("size negative"))?
+        ];
+        lgbm_call!(lightgbm_sys::LGBM_BoosterSaveModelToString(
+            self.handle,
+            start_iteration,
+            num_iteration,
+            0_i32,
+            buffer.len() as c_longlong,
+            &mut out_size as *mut _,
+            buffer.as_mut_ptr() as *mut c_char
+        ))?;
+
+        if buffer.pop() != Some(0) {
+            // this should never happen, unless lightgbm has a bug
+            panic!("write out of bounds happened in lightgbm call");
+        }
+
+        let cstring = CString::new(buffer).map_err(|e| Error::new(e.to_string()))?;
+        cstring
+            .into_string()
+            .map_err(|_| Error::new("can't convert model string to unicode"))
+    }
+}
+
+/// The features of a model by name, to align named input to them, see
+/// [`Booster::feature_name`].
+struct FeatureIndex {
+    names: Vec<String>,
+    positions: HashMap<String, usize>,
+}
+
+impl FeatureIndex {
+    fn new(booster: &Booster) -> Result<Self> {
+        let names = booster.feature_name()?;
+        let positions = names
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| (name.clone(), idx))
+            .collect();
+        Ok(FeatureIndex { names, positions })
+    }
+
+    /// The position of the feature `name`.
+    fn position(&self, name: &str) -> Result<usize> {
+        self.positions
+            .get(name)
+            .cloned()
+            .ok_or_else(|| Error::new(format!("{} is not a feature of the model", name)))
+    }
+
+    /// For every feature, the index of its column among the column `names`.
+    fn columns<S: AsRef<str>>(&self, names: &[S]) -> Result<Vec<usize>> {
+        let mut columns = vec![None; self.names.len()];
+        for (idx, name) in names.iter().enumerate() {
+            if columns[self.position(name.as_ref())?]
+                .replace(idx)
+                .is_some()
+            {
+                return Err(Error::new(format!(
+                    "column {} is given twice",
+                    name.as_ref()
+                )));
+            }
+        }
+        columns
+            .into_iter()
+            .zip(&self.names)
+            .map(|(column, name)| {
+                column.ok_or_else(|| {
+                    Error::new(format!(
+                        "the model feature {} is not one of the columns",
+                        name
+                    ))
+                })
+            })
+            .collect()
+    }
+}
+
+/// Reshape the flat predictions of all rows into the output layout of [`Booster::predict`].
+fn reshape_predictions(out_result: Vec<f64>, num_class: i32) -> Vec<Vec<f64>> {
+    // reshape for multiclass [1,2,3,4,5,6] -> [[1,2,3], [4,5,6]]  # 3 class
+    if num_class > 1 {
+        out_result
+            .chunks(num_class as usize)
+            .map(|x| x.to_vec())
+            .collect()
+    } else {
+        vec![out_result]
+    }
+}
+
+/// Native buffers allocated by `LGBM_BoosterPredictSparseOutput`, freed on drop.
+struct SparseOutput<P: IndexPtr, T: FeatureValue> {
+    indptr: *mut c_void,
+    indices: *mut i32,
+    data: *mut c_void,
+    _types: PhantomData<(P, T)>,
+}
+
+impl<P: IndexPtr, T: FeatureValue> Drop for SparseOutput<P, T> {
+    fn drop(&mut self) {
+        if !self.indptr.is_null() || !self.indices.is_null() || !self.data.is_null() {
+            lgbm_call!(lightgbm_sys::LGBM_BoosterFreePredictSparse(
+                self.indptr,
+                self.indices,
+                self.data,
+                P::DTYPE,
+                T::DTYPE
+            ))
+            .unwrap();
+        }
+    }
+}
+
+impl Drop for Booster {
+    fn drop(&mut self) {
+        lgbm_call!(lightgbm_sys::LGBM_BoosterFree(self.handle)).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{default_params, read_train_file, read_valid_file, train_booster};
+    use crate::Callback;
+    use serde_json::json;
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn predict() {
+        let params = json! {
+            {
+                "num_iterations": 10,
+                "objective": "binary",
+                "metric": "auc",
+                "data_random_seed": 0
+            }
+        };
+        let bst = train_booster(&params);
+        let feature = vec![vec![0.5; 28], vec![0.0; 28], vec![0.9; 28]];
+        let result = bst.predict(feature).unwrap();
+        let mut normalized_result = Vec::new();
+        for r in &result[0] {
+            normalized_result.push(if r > &0.5 { 1 } else { 0 });
+        }
+        assert_eq!(normalized_result, vec![0, 0, 1]);
+    }
+
+    #[test]
+    fn predict_with_type() {
+        let params = json! {
+            {
+                "num_iterations": 10,
+                "objective": "binary",
+                "data_random_seed": 0
+            }
+        };
+        let bst = train_booster(&params);
+        let feature = vec![vec![0.5; 28], vec![0.0; 28], vec![0.9; 28]];
+
+        let normal = bst
+            .predict_with_type(feature.clone(), PredictType::Normal)
+            .unwrap();
+        assert_eq!(normal.shape(), (3, 1, 1));
+        assert_eq!(
+            vec![normal.values().to_vec()],
+            bst.predict(feature.clone()).unwrap()
+        );
+
+        let raw = bst
+            .predict_with_type(feature.clone(), PredictType::RawScore)
+            .unwrap();
+        for (prob, score) in normal.values().iter().zip(raw.values()) {
+            assert!((prob - 1.0 / (1.0 + (-score).exp())).abs() < 1e-9);
+        }
+
+        let leaves = bst
+            .predict_with_type(feature.clone(), PredictType::LeafIndex)
+            .unwrap();
+        assert_eq!(leaves.shape(), (3, 10, 1));
+
+        let contrib = bst
+            .predict_with_type(feature, PredictType::Contrib)
+            .unwrap();
+        assert_eq!(contrib.shape(), (3, 1, 29));
+        for (row, score) in contrib.rows().zip(raw.values()) {
+            assert!((row.iter().sum::<f64>() - score).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn predict_with_range() {
+        let params = json! {
+            {
+                "num_iterations": 10,
+                "objective": "binary",
                 "data_random_seed": 0
             }
         };
-        let bst = _train_booster(&params);
+        let bst = train_booster(&params);
         let feature = vec![vec![0.5; 28], vec![0.0; 28], vec![0.9; 28]];
-        let result = bst.predict(feature).unwrap();
-        let mut normalized_result = Vec::new();
-        for r in &result[0] {
-            normalized_result.push(if r > &0.5 { 1 } else { 0 });
+        let raw = |range| {
+            bst.predict_with_range(feature.clone(), PredictType::RawScore, range)
+                .unwrap()
+        };
+
+        // the raw score is the sum over all trees
+        let all = raw(IterationRange::default());
+        let head = raw(IterationRange::new(0, Some(4)));
+        let tail = raw(IterationRange::new(4, None));
+        for i in 0..3 {
+            assert!((head.values()[i] + tail.values()[i] - all.values()[i]).abs() < 1e-9);
         }
-        assert_eq!(normalized_result, vec![0, 0, 1]);
+
+        let leaves = bst
+            .predict_with_range(
+                feature.clone(),
+                PredictType::LeafIndex,
+                IterationRange::new(2, Some(3)),
+            )
+            .unwrap();
+        assert_eq!(leaves.shape(), (3, 3, 1));
+    }
+
+    #[test]
+    fn save_string_with_range() {
+        let params = json! {
+            {
+                "num_iterations": 10,
+                "objective": "binary",
+                "data_random_seed": 0
+            }
+        };
+        let bst = train_booster(&params);
+        let model = bst
+            .save_string_with_range(IterationRange::new(0, Some(3)))
+            .unwrap();
+        let bst_head = Booster::from_string(&model).unwrap();
+        assert_eq!(bst_head.current_iteration(), Ok(3));
+        assert_eq!(
+            bst.save_file_size_with_range(IterationRange::new(0, Some(3))),
+            Ok(model.len() as u64)
+        );
+    }
+
+    #[test]
+    fn predict_dense() {
+        let bst = train_booster(&default_params());
+        let feature = vec![vec![0.5; 28], vec![0.0; 28], vec![0.75; 28]];
+        let expected = bst
+            .predict_with_type(feature.clone(), PredictType::Normal)
+            .unwrap();
+
+        let row_major: Vec<f32> = feature.iter().flatten().map(|v| *v as f32).collect();
+        let matrix = DenseMatrix::new(&row_major, 3, 28, Layout::RowMajor).unwrap();
+        let predictions = bst
+            .predict_dense(matrix, PredictType::Normal, IterationRange::default())
+            .unwrap();
+        assert_eq!(predictions, expected);
+
+        let column_major: Vec<f64> = (0..28)
+            .flat_map(|col| feature.iter().map(move |row| row[col]))
+            .collect();
+        let matrix = DenseMatrix::new(&column_major, 3, 28, Layout::ColumnMajor).unwrap();
+        let predictions = bst
+            .predict_dense(matrix, PredictType::Normal, IterationRange::default())
+            .unwrap();
+        assert_eq!(predictions, expected);
+    }
+
+    #[test]
+    fn predict_f32() {
+        let bst = train_booster(&default_params());
+        let feature: Vec<Vec<f32>> = vec![vec![0.5; 28], vec![0.0; 28], vec![0.75; 28]];
+        let expected = bst
+            .predict_with_type(feature.clone(), PredictType::Contrib)
+            .unwrap();
+        let feature_f64 = feature
+            .iter()
+            .map(|row| row.iter().map(|v| f64::from(*v)).collect())
+            .collect::<Vec<Vec<f64>>>();
+        assert_eq!(bst.predict(feature.clone()), bst.predict(feature_f64));
+
+        let data = feature.into_iter().flatten().collect::<Vec<_>>();
+        let matrix = DenseMatrix::new(&data, 3, 28, Layout::RowMajor).unwrap();
+        let mut output = vec![0.0_f32; 3 * 29];
+        bst.predict_dense_into(
+            matrix,
+            PredictType::Contrib,
+            IterationRange::default(),
+            &mut output,
+        )
+        .unwrap();
+        for (out, value) in output.iter().zip(expected.values()) {
+            assert_eq!(*out, *value as f32);
+        }
+        let mut output = vec![0.0_f64; 3];
+        assert!(bst
+            .predict_dense_into(
+                matrix,
+                PredictType::Contrib,
+                IterationRange::default(),
+                &mut output
+            )
+            .is_err());
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn predict_array() {
+        use ndarray::Array2;
+
+        let bst = train_booster(&default_params());
+        let feature = vec![vec![0.5; 28], vec![0.0; 28], vec![0.75; 28]];
+        let expected = bst.predict(feature.clone()).unwrap();
+        let data = Array2::from_shape_vec((3, 28), feature.concat()).unwrap();
+
+        let predictions = bst.predict_array(data.view(), PredictType::Normal).unwrap();
+        assert_eq!(predictions.dim(), (3, 1));
+        assert_eq!(predictions.column(0).to_vec(), expected[0]);
+
+        let fortran = data.t().as_standard_layout().into_owned().reversed_axes();
+        let contributions = bst.predict_contrib_array(fortran.view()).unwrap();
+        assert_eq!(contributions.dim(), (3, 1, 29));
+        let raw = bst
+            .predict_array(data.view(), PredictType::RawScore)
+            .unwrap();
+        for row in 0..3 {
+            let sum = contributions.slice(ndarray::s![row, 0, ..]).sum();
+            assert!((sum - raw[[row, 0]]).abs() < 1e-9);
+        }
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn predict_arrow() {
+        use arrow::array::{ArrayRef, Float64Array};
+        use std::sync::Arc;
+
+        let bst = train_booster(&default_params());
+        let feature = vec![vec![0.5; 28], vec![0.0; 28], vec![0.75; 28]];
+        let expected = bst
+            .predict_with_type(feature.clone(), PredictType::RawScore)
+            .unwrap();
+
+        let columns = (0..28).map(|col| {
+            let values = feature.iter().map(|row| row[col]).collect::<Vec<_>>();
+            (
+                format!("Column_{}", col),
+                Arc::new(Float64Array::from(values)) as ArrayRef,
+            )
+        });
+        let batch = RecordBatch::try_from_iter(columns).unwrap();
+        let predictions = bst
+            .predict_arrow(&[batch], PredictType::RawScore, IterationRange::default())
+            .unwrap();
+        assert_eq!(predictions, expected);
+    }
+
+    #[cfg(feature = "dataframe")]
+    #[test]
+    fn predict_dataframe() {
+        use crate::DataFrameOptions;
+        use polars::df;
+
+        let df: DataFrame = df![
+            "amount" => [Some(10.0), Some(7.5), None, Some(2.0), Some(1.0), Some(3.0)],
+            "age" => [31, 45, 27, 60, 38, 52],
+            "country" => ["DE", "FR", "DE", "US", "FR", "DE"],
+            "label" => [0.0, 0.0, 1.0, 1.0, 0.0, 1.0]
+        ]
+        .unwrap();
+        let params = json! {
+            {
+                "num_iterations": 2,
+                "objective": "binary",
+                "min_data_in_leaf": 1,
+                "min_data_in_bin": 1
+            }
+        };
+        let features = df.drop("label").unwrap();
+        let mut encoder = CategoryEncoder::new();
+        let options = DataFrameOptions::new("label");
+        let dataset = Dataset::from_dataframe_with_options(&df, &options, &mut encoder).unwrap();
+        let bst = Booster::train(dataset, &params).unwrap();
+        assert_eq!(
+            bst.feature_name().unwrap(),
+            vec!["amount", "age", "country"]
+        );
+
+        let expected = bst
+            .predict_dataframe(
+                &features,
+                &encoder,
+                PredictType::RawScore,
+                IterationRange::default(),
+            )
+            .unwrap();
+        assert_eq!(expected.num_rows(), 6);
+
+        // in another process the categories are seen in another order
+        let model_string = bst.save_string_with_encoder(&encoder).unwrap();
+        let loaded = Booster::from_string(&model_string).unwrap();
+        let loaded_encoder = CategoryEncoder::from_model_string(&model_string)
+            .unwrap()
+            .unwrap();
+        let reversed = features
+            .reverse()
+            .select(["country", "age", "amount"])
+            .unwrap();
+        let predictions = loaded
+            .predict_dataframe(
+                &reversed,
+                &loaded_encoder,
+                PredictType::RawScore,
+                IterationRange::default(),
+            )
+            .unwrap();
+        for row in 0..6 {
+            assert_eq!(predictions.row(row), expected.row(5 - row));
+        }
+
+        // columns which are not features are rejected like in the other named predictions
+        assert!(bst
+            .predict_dataframe(
+                &df,
+                &encoder,
+                PredictType::RawScore,
+                IterationRange::default()
+            )
+            .is_err());
+        let incomplete = df.select(["amount"]).unwrap();
+        assert!(bst
+            .predict_dataframe(
+                &incomplete,
+                &encoder,
+                PredictType::RawScore,
+                IterationRange::default()
+            )
+            .is_err());
+        // the categories of the model are unknown to an empty encoder
+        assert!(bst
+            .predict_dataframe(
+                &features,
+                &CategoryEncoder::new(),
+                PredictType::RawScore,
+                IterationRange::default()
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn rank() {
+        // 10 queries of 4 candidates, the relevance grows with the first feature
+        let mut data = Vec::new();
+        let mut relevance = Vec::new();
+        for query in 0..10 {
+            for candidate in 0..4 {
+                let score = ((query * 7 + candidate * 3) % 4) as f64;
+                data.push(vec![score, (query % 3) as f64]);
+                relevance.push(score as f32);
+            }
+        }
+        let flat_data = data.iter().flatten().cloned().collect::<Vec<_>>();
+        let mut dataset = Dataset::from_mat(data, relevance).unwrap();
+        dataset.set_groups(&[4; 10]).unwrap();
+        let params = json! {
+            {
+                "num_iterations": 10,
+                "objective": "lambdarank",
+                "min_data_in_leaf": 1,
+                "min_data_in_bin": 1
+            }
+        };
+        let bst = Booster::train(dataset, &params).unwrap();
+
+        let matrix = DenseMatrix::new(&flat_data[..16], 8, 2, Layout::RowMajor).unwrap();
+        let rankings = bst.rank(matrix, &[4, 4]).unwrap();
+        assert_eq!(rankings.len(), 2);
+        for (query, ranking) in rankings.iter().enumerate() {
+            let best = flat_data[(query * 4 + ranking[0]) * 2];
+            assert_eq!(best, 3.0);
+            let mut sorted = ranking.clone();
+            sorted.sort();
+            assert_eq!(sorted, vec![0, 1, 2, 3]);
+        }
+        assert!(bst.rank(matrix, &[4, 3]).is_err());
+    }
+
+    #[test]
+    fn predict_sparse() {
+        let bst = train_booster(&default_params());
+        let feature = vec![
+            vec![0.5; 28],
+            vec![0.0; 28],
+            (0..28)
+                .map(|i| if i % 3 == 0 { 0.75 } else { 0.0 })
+                .collect(),
+        ];
+        let expected = bst.predict(feature.clone()).unwrap();
+
+        let mut indptr = vec![0];
+        let mut indices = Vec::new();
+        let mut values = Vec::new();
+        for row in &feature {
+            for (col, value) in row.iter().enumerate() {
+                if *value != 0.0 {
+                    indices.push(col as i32);
+                    values.push(*value);
+                }
+            }
+            indptr.push(values.len() as i32);
+        }
+        assert_eq!(
+            bst.predict_csr(&indptr, &indices, &values, 28).unwrap(),
+            expected
+        );
+
+        let mut col_ptr = vec![0_i64];
+        let mut row_indices = Vec::new();
+        let mut col_values = Vec::new();
+        for col in 0..28 {
+            for (row, features) in feature.iter().enumerate() {
+                if features[col] != 0.0 {
+                    row_indices.push(row as i32);
+                    col_values.push(features[col] as f32);
+                }
+            }
+            col_ptr.push(col_values.len() as i64);
+        }
+        assert_eq!(
+            bst.predict_csc(&col_ptr, &row_indices, &col_values, 3)
+                .unwrap(),
+            expected
+        );
+
+        let row_start = indptr[2] as usize;
+        let single = bst
+            .predict_csr_single_row(&indices[row_start..], &values[row_start..], 28)
+            .unwrap();
+        assert_eq!(single, vec![expected[0][2]]);
+        assert!(bst.predict_csr_single_row(&[28], &[1.0], 28).is_err());
+    }
+
+    #[test]
+    fn predict_contrib_sparse() {
+        let bst = train_booster(&default_params());
+        let feature = vec![
+            vec![0.5; 28],
+            (0..28)
+                .map(|i| if i % 3 == 0 { 0.75 } else { 0.0 })
+                .collect(),
+        ];
+        let dense = bst
+            .predict_with_type(feature.clone(), PredictType::Contrib)
+            .unwrap();
+
+        let mut indptr = vec![0_i64];
+        let mut indices = Vec::new();
+        let mut values = Vec::new();
+        for row in &feature {
+            for (col, value) in row.iter().enumerate() {
+                if *value != 0.0 {
+                    indices.push(col as i32);
+                    values.push(*value);
+                }
+            }
+            indptr.push(values.len() as i64);
+        }
+        let sparse = bst
+            .predict_contrib_csr(&indptr, &indices, &values, 28)
+            .unwrap();
+        assert_eq!(sparse.len(), 1);
+        assert_eq!(sparse[0].shape(), (2, 29));
+        for row in 0..2 {
+            for col in 0..29 {
+                let value = sparse[0].get(row, col).unwrap_or(0.0);
+                assert!((value - dense.get(row, 0, col)).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn train_with_valid_sets() {
+        let params = json! {
+            {
+                "num_iterations": 10,
+                "objective": "binary",
+                "metric": "auc,binary_logloss",
+                "data_random_seed": 0
+            }
+        };
+        let train = read_train_file().unwrap();
+        let valid = read_valid_file(&train).unwrap();
+        let (_, history) =
+            Booster::train_with_valid_sets(train, vec![("valid", valid)], &params).unwrap();
+        let valid_history = &history["valid"];
+        assert_eq!(valid_history.len(), 2);
+        let auc = &valid_history["auc"];
+        assert_eq!(auc.len(), valid_history["binary_logloss"].len());
+        assert!(!auc.is_empty());
+        assert!(auc.iter().all(|v| *v > 0.5 && *v <= 1.0));
+    }
+
+    #[test]
+    fn train_runs_num_iterations() {
+        let params = default_params();
+        let bst = train_booster(&params);
+        let model = bst.save_string().unwrap();
+        assert_eq!(model.matches("Tree=").count(), 1);
+
+        let mut params = default_params();
+        params["num_iterations"] = json!("5");
+        assert!(Booster::train(read_train_file().unwrap(), &params).is_err());
+    }
+
+    #[test]
+    fn early_stopping() {
+        let params = json! {
+            {
+                "num_iterations": 1000,
+                "learning_rate": 0.5,
+                "objective": "binary",
+                "metric": "binary_logloss",
+                "early_stopping_round": 3,
+                "data_random_seed": 0
+            }
+        };
+        let train = read_train_file().unwrap();
+        let valid = read_valid_file(&train).unwrap();
+        let (bst, history) =
+            Booster::train_with_valid_sets(train, vec![("valid", valid)], &params).unwrap();
+        let logloss = &history["valid"]["binary_logloss"];
+        assert!(logloss.len() < 1000);
+
+        let best_iteration = bst.best_iteration().unwrap();
+        assert_eq!(logloss.len(), best_iteration as usize + 3);
+        let best = logloss[best_iteration as usize - 1];
+        assert!(logloss.iter().all(|v| *v >= best));
+
+        let model = bst.save_string().unwrap();
+        assert_eq!(model.matches("Tree=").count(), best_iteration as usize);
+    }
+
+    #[test]
+    fn early_stopping_metric() {
+        let params = json! {
+            {
+                "num_iterations": 1000,
+                "learning_rate": 0.5,
+                "objective": "binary",
+                "metric": ["auc", "binary_logloss"],
+                "early_stopping_round": 3,
+                "data_random_seed": 0
+            }
+        };
+        let train = read_train_file().unwrap();
+        let valid = read_valid_file(&train).unwrap();
+        let options = TrainOptions::new()
+            .valid_set("valid", valid)
+            .early_stopping_metric("binary_logloss");
+        let (bst, history) = Booster::train_with_options(train, &params, options).unwrap();
+        let logloss = &history["valid"]["binary_logloss"];
+        let best_iteration = bst.best_iteration().unwrap();
+        assert_eq!(logloss.len(), best_iteration as usize + 3);
+        let best = logloss[best_iteration as usize - 1];
+        assert!(logloss.iter().all(|v| *v >= best));
+    }
+
+    #[test]
+    fn train_with_objective() {
+        let params = json! {
+            {
+                "num_iterations": 10,
+                "metric": "auc",
+                "data_random_seed": 0
+            }
+        };
+        let train = read_train_file().unwrap();
+        let valid = read_valid_file(&train).unwrap();
+        let mut num_calls = 0;
+        let objective = |scores: &[f64], labels: &[f32], weights: Option<&[f32]>| {
+            num_calls += 1;
+            assert_eq!(scores.len(), labels.len());
+            assert!(weights.is_none());
+            scores
+                .iter()
+                .zip(labels)
+                .map(|(score, label)| {
+                    let p = 1.0 / (1.0 + (-score).exp());
+                    ((p - *label as f64) as f32, (p * (1.0 - p)) as f32)
+                })
+                .unzip()
+        };
+        let (_, history) =
+            Booster::train_with_objective(train, vec![("valid", valid)], &params, objective)
+                .unwrap();
+        assert_eq!(num_calls, 10);
+        assert!(*history["valid"]["auc"].last().unwrap() > 0.7);
+    }
+
+    #[test]
+    fn train_with_objective_wrong_len() {
+        let params = default_params();
+        let train = read_train_file().unwrap();
+        let objective = |_: &[f64], _: &[f32], _: Option<&[f32]>| (vec![0.0], vec![1.0]);
+        assert!(Booster::train_with_objective(train, vec![], &params, objective).is_err());
+    }
+
+    #[test]
+    fn train_with_custom_metric() {
+        let params = json! {
+            {
+                "num_iterations": 10,
+                "objective": "binary",
+                "metric": "auc",
+                "data_random_seed": 0
+            }
+        };
+        let train = read_train_file().unwrap();
+        let valid = read_valid_file(&train).unwrap();
+        let accuracy = CustomMetric::new("accuracy", true, |preds, dataset| {
+            let labels = dataset.get_label().unwrap();
+            let correct = preds
+                .iter()
+                .zip(&labels)
+                .filter(|(p, l)| (**p > 0.5) == (**l > 0.5))
+                .count();
+            correct as f64 / labels.len() as f64
+        });
+        let options = TrainOptions::new()
+            .valid_set("valid", valid)
+            .metric(accuracy);
+        let (_, history) = Booster::train_with_options(train, &params, options).unwrap();
+
+        // built-in metrics are not computed on the training data by default
+        assert_eq!(history["training"].len(), 1);
+        assert_eq!(history["training"]["accuracy"].len(), 10);
+        assert_eq!(history["valid"].len(), 2);
+        assert_eq!(history["valid"]["auc"].len(), 10);
+        assert!(history["valid"]["accuracy"]
+            .iter()
+            .all(|v| *v > 0.5 && *v <= 1.0));
+    }
+
+    #[test]
+    fn custom_metric_early_stopping() {
+        let params = json! {
+            {
+                "num_iterations": 100,
+                "objective": "binary",
+                "metric": "None",
+                "early_stopping_round": 2,
+                "data_random_seed": 0
+            }
+        };
+        let train = read_train_file().unwrap();
+        let valid = read_valid_file(&train).unwrap();
+        // never improves after the first iteration
+        let constant = CustomMetric::new("constant", true, |_, _| 1.0);
+        let options = TrainOptions::new()
+            .valid_set("valid", valid)
+            .metric(constant);
+        let (bst, history) = Booster::train_with_options(train, &params, options).unwrap();
+        assert_eq!(history["valid"]["constant"].len(), 3);
+        assert_eq!(bst.best_iteration(), Some(1));
+    }
+
+    #[test]
+    fn step_wise_training() {
+        let params = default_params();
+        let train = read_train_file().unwrap();
+        let valid = read_valid_file(&train).unwrap();
+        let mut bst = Booster::new(train, &params).unwrap();
+        bst.add_valid_set("valid", valid).unwrap();
+        for _ in 0..3 {
+            assert_eq!(bst.update_one_iter(), Ok(false));
+        }
+        assert_eq!(bst.current_iteration(), Ok(3));
+        bst.rollback_one_iter().unwrap();
+        assert_eq!(bst.current_iteration(), Ok(2));
+
+        let results = bst.eval().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].dataset_name, "valid");
+        assert_eq!(results[0].metric_name, "auc");
+        assert!(results[0].higher_is_better);
+
+        bst.reset_parameter(&json! {{"learning_rate": 0.05}})
+            .unwrap();
+        assert_eq!(bst.update_one_iter(), Ok(false));
+        assert_eq!(bst.current_iteration(), Ok(3));
+    }
+
+    #[test]
+    fn add_valid_set_name_in_use() {
+        let params = default_params();
+        let train = read_train_file().unwrap();
+        let valid = read_valid_file(&train).unwrap();
+        let mut bst = Booster::new(train, &params).unwrap();
+        assert!(bst.add_valid_set("training", valid).is_err());
+    }
+
+    #[test]
+    fn trained_booster_keeps_training() {
+        let params = default_params();
+        let mut bst = train_booster(&params);
+        assert_eq!(bst.current_iteration(), Ok(1));
+        bst.reset_parameter(&json! {{"learning_rate": 0.05}})
+            .unwrap();
+        bst.update_one_iter().unwrap();
+        assert_eq!(bst.current_iteration(), Ok(2));
+        bst.rollback_one_iter().unwrap();
+        assert_eq!(bst.current_iteration(), Ok(1));
+    }
+
+    #[test]
+    fn loaded_booster_is_not_trainable() {
+        let bst = train_booster(&default_params());
+        let mut loaded = Booster::from_string(&bst.save_string().unwrap()).unwrap();
+        assert!(loaded.update_one_iter().is_err());
+        let train = read_train_file().unwrap();
+        let valid = read_valid_file(&train).unwrap();
+        assert!(loaded.add_valid_set("valid", valid).is_err());
+    }
+
+    #[test]
+    fn callbacks() {
+        struct StopAfter {
+            iterations: usize,
+            before: usize,
+            after: usize,
+        }
+
+        impl Callback for StopAfter {
+            fn before_iteration(&mut self, booster: &mut Booster, iteration: usize) -> Result<()> {
+                assert_eq!(booster.current_iteration(), Ok(iteration as i32));
+                self.before += 1;
+                Ok(())
+            }
+
+            fn after_iteration(
+                &mut self,
+                _booster: &mut Booster,
+                iteration: usize,
+                results: &[EvalResult],
+            ) -> Result<CallbackAction> {
+                assert_eq!(results.len(), 1);
+                self.after += 1;
+                Ok(if iteration + 1 == self.iterations {
+                    CallbackAction::Stop
+                } else {
+                    CallbackAction::Continue
+                })
+            }
+        }
+
+        let params = json! {
+            {
+                "num_iterations": 10,
+                "objective": "binary",
+                "metric": "auc",
+                "data_random_seed": 0
+            }
+        };
+        let train = read_train_file().unwrap();
+        let valid = read_valid_file(&train).unwrap();
+        let mut callback = StopAfter {
+            iterations: 3,
+            before: 0,
+            after: 0,
+        };
+        let options = TrainOptions::new()
+            .valid_set("valid", valid)
+            .callback(&mut callback);
+        let (bst, history) = Booster::train_with_options(train, &params, options).unwrap();
+        assert_eq!(history["valid"]["auc"].len(), 3);
+        assert_eq!(bst.current_iteration(), Ok(3));
+        assert_eq!(callback.before, 3);
+        assert_eq!(callback.after, 3);
+    }
+
+    #[test]
+    fn train_with_typed_params() {
+        use crate::{Metric, Objective, TrainParamsBuilder};
+
+        let params = TrainParamsBuilder::default()
+            .objective(Objective::Binary)
+            .metric(vec![Metric::Auc, Metric::BinaryLogloss])
+            .num_iterations(5)
+            .num_leaves(7)
+            .data_random_seed(0)
+            .build()
+            .unwrap();
+        let train = read_train_file().unwrap();
+        let valid = read_valid_file(&train).unwrap();
+        let (bst, history) =
+            Booster::train_with_valid_sets(train, vec![("valid", valid)], &params.to_value())
+                .unwrap();
+        assert_eq!(history["valid"].len(), 2);
+        assert_eq!(history["valid"]["auc"].len(), 5);
+        let model = bst.save_string().unwrap();
+        assert!(model.contains("[num_leaves: 7]"));
+    }
+
+    #[test]
+    fn train_with_list_params() {
+        let params = json! {
+            {
+                "num_iterations": 3,
+                "objective": "binary",
+                "metric": ["auc", "binary_logloss"],
+                "monotone_constraints": vec![1; 28],
+                "data_random_seed": 0
+            }
+        };
+        let train = read_train_file().unwrap();
+        let valid = read_valid_file(&train).unwrap();
+        let (bst, history) =
+            Booster::train_with_valid_sets(train, vec![("valid", valid)], &params).unwrap();
+        assert_eq!(history["valid"].len(), 2);
+        let model = bst.save_string().unwrap();
+        assert!(model.contains("[objective: binary]"));
+        assert!(model.contains("[monotone_constraints: 1,1,1"));
+    }
+
+    #[test]
+    fn train_with_invalid_params() {
+        let params = json! {{"objective": {"name": "binary"}}};
+        let train = read_train_file().unwrap();
+        assert!(Booster::train(train, &params).is_err());
+    }
+
+    #[test]
+    fn train_with_param_aliases() {
+        let params = json! {
+            {
+                "num_boost_round": 3,
+                "application": "binary",
+                "data_random_seed": 0
+            }
+        };
+        let bst = train_booster(&params);
+        let model = bst.save_string().unwrap();
+        assert_eq!(model.matches("Tree=").count(), 3);
+    }
+
+    #[test]
+    fn train_with_unknown_params() {
+        let params = json! {{"objective": "binary", "num_leavs": 7}};
+        let train = read_train_file().unwrap();
+        assert!(Booster::train(train, &params).is_err());
+
+        let params = json! {{"num_iterations": 3, "n_estimators": 5}};
+        let train = read_train_file().unwrap();
+        assert!(Booster::train(train, &params).is_err());
     }
 
     #[test]
     fn num_feature() {
-        let params = _default_params();
-        let bst = _train_booster(&params);
+        let params = default_params();
+        let bst = train_booster(&params);
         let num_feature = bst.num_feature().unwrap();
         assert_eq!(num_feature, 28);
     }
 
     #[test]
     fn feature_importance() {
-        let params = _default_params();
-        let bst = _train_booster(&params);
+        let params = default_params();
+        let bst = train_booster(&params);
         let feature_importance = bst.feature_importance().unwrap();
-        assert_eq!(feature_importance, vec![0.0; 28]);
+        assert_eq!(feature_importance.len(), 28);
+        assert!(feature_importance.iter().any(|v| *v > 0.0));
     }
 
     #[test]
     fn feature_name() {
-        let params = _default_params();
-        let bst = _train_booster(&params);
+        let params = default_params();
+        let bst = train_booster(&params);
         let feature_name = bst.feature_name().unwrap();
         let target = (0..28).map(|i| format!("Column_{}", i)).collect::<Vec<_>>();
         assert_eq!(feature_name, target);
     }
 
+    #[test]
+    fn predict_named() {
+        let data = vec![
+            vec![1.0, 0.1, 0.2],
+            vec![0.7, 0.4, 0.5],
+            vec![0.9, 0.8, 0.5],
+            vec![0.2, 0.2, 0.8],
+            vec![0.1, 0.7, 1.0],
+        ];
+        let mut dataset = Dataset::from_mat(data, vec![0.0, 0.0, 0.0, 1.0, 1.0]).unwrap();
+        dataset
+            .set_feature_names(&["amount", "age", "score"])
+            .unwrap();
+        let params = json! {
+            {
+                "num_iterations": 3,
+                "objective": "binary",
+                "min_data_in_leaf": 1,
+                "min_data_in_bin": 1
+            }
+        };
+        let bst = Booster::train(dataset, &params).unwrap();
+        assert_eq!(bst.feature_name().unwrap(), vec!["amount", "age", "score"]);
+        assert!(bst
+            .validate_feature_names(&["amount", "age", "score"])
+            .is_ok());
+        assert!(bst
+            .validate_feature_names(&["age", "amount", "score"])
+            .is_err());
+
+        let expected = bst
+            .predict_with_type(
+                vec![vec![0.2, 0.2, 0.8], vec![0.9, f64::NAN, f64::NAN]],
+                PredictType::RawScore,
+            )
+            .unwrap();
+        let mut complete = HashMap::new();
+        complete.insert("score", 0.8);
+        complete.insert("amount", 0.2);
+        complete.insert("age", 0.2);
+        let mut incomplete = HashMap::new();
+        incomplete.insert("amount", 0.9);
+        let predictions = bst
+            .predict_named_rows(
+                &[complete, incomplete.clone()],
+                PredictType::RawScore,
+                IterationRange::default(),
+            )
+            .unwrap();
+        assert_eq!(predictions, expected);
+
+        incomplete.insert("unknown", 1.0);
+        assert!(bst
+            .predict_named_rows(
+                &[incomplete],
+                PredictType::RawScore,
+                IterationRange::default()
+            )
+            .is_err());
+
+        let values = [0.2, 0.2, 0.8];
+        let matrix = DenseMatrix::new(&values, 1, 3, Layout::RowMajor).unwrap();
+        let names = ["amount", "age", "score"];
+        let predictions = bst
+            .predict_dense_named(
+                matrix,
+                &names,
+                PredictType::RawScore,
+                IterationRange::default(),
+            )
+            .unwrap();
+        assert_eq!(predictions.row(0), expected.row(0));
+        let names = ["score", "age", "amount"];
+        assert!(bst
+            .predict_dense_named(
+                matrix,
+                &names,
+                PredictType::RawScore,
+                IterationRange::default()
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn predict_columns() {
+        let amount = [10.0, 7.5, 2.0, 1.0, 3.0, 8.0, 0.5, 9.0];
+        let city = [
+            "Berlin", "Paris", "Berlin", "Rome", "Paris", "Berlin", "Rome", "Paris",
+        ];
+        let label = [0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0];
+        let mut encoder = CategoryEncoder::new();
+        let columns = [
+            ("amount", Column::Numeric(&amount)),
+            ("city", Column::Categorical(&city)),
+        ];
+        let dataset = Dataset::from_columns(
+            &columns,
+            &label,
+            &mut encoder,
+            &crate::DatasetParams::default(),
+        )
+        .unwrap();
+        let params = json! {
+            {
+                "num_iterations": 3,
+                "objective": "binary",
+                "min_data_in_leaf": 1,
+                "min_data_in_bin": 1,
+                "min_data_per_group": 1,
+                "cat_smooth": 1
+            }
+        };
+        let bst = Booster::train(dataset, &params).unwrap();
+
+        let model_string = bst.save_string_with_encoder(&encoder).unwrap();
+        let loaded = Booster::from_string(&model_string).unwrap();
+        let loaded_encoder = CategoryEncoder::from_model_string(&model_string)
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded_encoder, encoder);
+
+        let codes = encoder.encode("city", &city).unwrap();
+        let data = amount
+            .iter()
+            .zip(&codes)
+            .map(|(amount, code)| vec![*amount, *code])
+            .collect::<Vec<_>>();
+        let expected = bst.predict_with_type(data, PredictType::RawScore).unwrap();
+        let reordered = [
+            ("city", Column::Categorical(&city)),
+            ("amount", Column::Numeric(&amount)),
+        ];
+        let predictions = loaded
+            .predict_columns(
+                &reordered,
+                &loaded_encoder,
+                PredictType::RawScore,
+                IterationRange::default(),
+            )
+            .unwrap();
+        assert_eq!(predictions, expected);
+
+        let missing = [("amount", Column::Numeric(&amount))];
+        let unknown = [
+            ("city", Column::Categorical(&city)),
+            ("unused", Column::Numeric(&[0.0; 8])),
+            ("amount", Column::Numeric(&amount)),
+        ];
+        let twice = [
+            ("city", Column::Categorical(&city)),
+            ("amount", Column::Numeric(&amount)),
+            ("amount", Column::Numeric(&amount)),
+        ];
+        for columns in [&missing[..], &unknown[..], &twice[..]].iter() {
+            assert!(loaded
+                .predict_columns(
+                    columns,
+                    &loaded_encoder,
+                    PredictType::RawScore,
+                    IterationRange::default()
+                )
+                .is_err());
+        }
+    }
+
     #[test]
     fn save_file() {
-        let params = _default_params();
-        let bst = _train_booster(&params);
+        let params = default_params();
+        let bst = train_booster(&params);
         assert_eq!(bst.save_file(&"./test/test_save_file.output"), Ok(()));
         assert!(Path::new("./test/test_save_file.output").exists());
         let _ = fs::remove_file("./test/test_save_file.output");
@@ -378,8 +2742,8 @@ mod tests {
 
     #[test]
     fn save_file_size() {
-        let params = _default_params();
-        let bst = _train_booster(&params);
+        let params = default_params();
+        let bst = train_booster(&params);
         let filename = "./test/test_save_file_size.output";
         assert_eq!(bst.save_file(filename), Ok(()));
         let file_size = Path::new(filename).metadata().unwrap().len();
@@ -390,8 +2754,8 @@ mod tests {
 
     #[test]
     fn save_string() {
-        let params = _default_params();
-        let bst = _train_booster(&params);
+        let params = default_params();
+        let bst = train_booster(&params);
         let filename = "./test/test_save_string.output";
         assert_eq!(bst.save_file(&filename), Ok(()));
         assert!(Path::new(&filename).exists());
@@ -0,0 +1,338 @@
+//! Categorical features and the encoding of string categories.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::{self, Value};
+
+use crate::{Error, Result};
+
+/// Prefix of the line holding a [`CategoryEncoder`] in a model string.
+const MODEL_STRING_KEY: &str = "category_encoder:";
+
+/// A feature declared as categorical, by its index or by its name.
+///
+/// Example
+/// ```
+/// use lightgbm::CategoricalFeature;
+///
+/// let categorical: Vec<CategoricalFeature> = vec![2.into(), "city".into()];
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CategoricalFeature {
+    Index(usize),
+    Name(String),
+}
+
+impl From<usize> for CategoricalFeature {
+    fn from(index: usize) -> Self {
+        CategoricalFeature::Index(index)
+    }
+}
+
+impl<'a> From<&'a str> for CategoricalFeature {
+    fn from(name: &'a str) -> Self {
+        CategoricalFeature::Name(name.to_string())
+    }
+}
+
+impl From<String> for CategoricalFeature {
+    fn from(name: String) -> Self {
+        CategoricalFeature::Name(name)
+    }
+}
+
+/// The `categorical_feature` dataset parameter declaring `categorical` features, whose names
+/// are resolved against `feature_names`. Empty if no feature is categorical.
+pub(crate) fn categorical_feature_param<S: AsRef<str>>(
+    categorical: &[CategoricalFeature],
+    feature_names: &[S],
+) -> Result<String> {
+    if categorical.is_empty() {
+        return Ok(String::new());
+    }
+    let indices = categorical
+        .iter()
+        .map(|feature| match feature {
+            CategoricalFeature::Index(index) if *index < feature_names.len() => Ok(*index),
+            CategoricalFeature::Index(index) => Err(Error::new(format!(
+                "categorical feature {} is out of range for {} features",
+                index,
+                feature_names.len()
+            ))),
+            CategoricalFeature::Name(name) => feature_names
+                .iter()
+                .position(|feature_name| feature_name.as_ref() == name)
+                .ok_or_else(|| Error::new(format!("unknown categorical feature {}", name))),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let indices = indices
+        .iter()
+        .map(|index| index.to_string())
+        .collect::<Vec<_>>();
+    Ok(format!("categorical_feature={}", indices.join(",")))
+}
+
+/// A column of raw feature values, see [`crate::Dataset::from_columns`].
+#[derive(Clone, Copy, Debug)]
+pub enum Column<'a> {
+    Numeric(&'a [f64]),
+    /// String categories, encoded by a [`CategoryEncoder`].
+    Categorical(&'a [&'a str]),
+}
+
+impl<'a> Column<'a> {
+    pub fn len(&self) -> usize {
+        match self {
+            Column::Numeric(values) => values.len(),
+            Column::Categorical(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Categories of one feature, in the order of their codes.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Vocabulary {
+    categories: Vec<String>,
+    codes: HashMap<String, i32>,
+}
+
+impl Vocabulary {
+    fn insert(&mut self, category: &str) {
+        if !self.codes.contains_key(category) {
+            self.codes
+                .insert(category.to_string(), self.categories.len() as i32);
+            self.categories.push(category.to_string());
+        }
+    }
+}
+
+/// Maps string categories of features to the non-negative integer codes LightGBM expects for
+/// categorical features.
+///
+/// Codes are assigned in the order categories are first seen and never change, so a fitted
+/// encoder has to be kept together with the model, e.g. with
+/// [`crate::Booster::save_string_with_encoder`]. Categories unknown to the encoder are encoded
+/// as missing values.
+///
+/// Example
+/// ```
+/// use lightgbm::CategoryEncoder;
+///
+/// let mut encoder = CategoryEncoder::new();
+/// encoder.fit("city", &["Berlin", "Paris", "Berlin"]);
+/// assert_eq!(encoder.code("city", "Paris"), Some(1));
+/// let codes = encoder.encode("city", &["Paris", "Rome"]).unwrap();
+/// assert_eq!(codes[0], 1.0);
+/// assert!(codes[1].is_nan());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CategoryEncoder {
+    vocabularies: BTreeMap<String, Vocabulary>,
+}
+
+impl CategoryEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Assign codes to the categories of `feature` in `values` which have none yet.
+    pub fn fit<S: AsRef<str>>(&mut self, feature: &str, values: &[S]) {
+        let vocabulary = self.vocabularies.entry(feature.to_string()).or_default();
+        for value in values {
+            vocabulary.insert(value.as_ref());
+        }
+    }
+
+    /// Names of the features known to the encoder.
+    pub fn features(&self) -> impl Iterator<Item = &str> {
+        self.vocabularies.keys().map(String::as_str)
+    }
+
+    /// The categories of `feature` in the order of their codes.
+    pub fn categories(&self, feature: &str) -> Option<&[String]> {
+        self.vocabularies
+            .get(feature)
+            .map(|vocabulary| vocabulary.categories.as_slice())
+    }
+
+    /// The code of `category` of `feature`, `None` if it is unknown.
+    pub fn code(&self, feature: &str, category: &str) -> Option<i32> {
+        self.vocabularies
+            .get(feature)
+            .and_then(|vocabulary| vocabulary.codes.get(category))
+            .cloned()
+    }
+
+    /// Encode `values` of `feature`, unknown categories become `NaN`, i.e. missing.
+    pub fn encode<S: AsRef<str>>(&self, feature: &str, values: &[S]) -> Result<Vec<f64>> {
+        let vocabulary = self
+            .vocabularies
+            .get(feature)
+            .ok_or_else(|| Error::new(format!("feature {} is unknown to the encoder", feature)))?;
+        Ok(values
+            .iter()
+            .map(|value| {
+                vocabulary
+                    .codes
+                    .get(value.as_ref())
+                    .map_or(f64::NAN, |code| f64::from(*code))
+            })
+            .collect())
+    }
+
+    /// Append the encoder to `model_string` as a line LightGBM ignores when loading the model.
+    pub(crate) fn append_to_model_string(&self, model_string: &mut String) {
+        let categories = self
+            .vocabularies
+            .iter()
+            .map(|(feature, vocabulary)| {
+                (feature.clone(), Value::from(vocabulary.categories.clone()))
+            })
+            .collect::<serde_json::Map<_, _>>();
+        if !model_string.ends_with('\n') {
+            model_string.push('\n');
+        }
+        model_string.push_str(MODEL_STRING_KEY);
+        model_string.push_str(&Value::Object(categories).to_string());
+        model_string.push('\n');
+    }
+
+    /// Read the encoder saved with a model by [`crate::Booster::save_string_with_encoder`],
+    /// `None` if the model string contains no encoder.
+    ///
+    /// Example
+    /// ```no_run
+    /// use lightgbm::{Booster, CategoryEncoder};
+    ///
+    /// let model_string = std::fs::read_to_string("path/to/model.txt").unwrap();
+    /// let booster = Booster::from_string(&model_string).unwrap();
+    /// let encoder = CategoryEncoder::from_model_string(&model_string).unwrap();
+    /// ```
+    pub fn from_model_string(model_string: &str) -> Result<Option<Self>> {
+        let line = match model_string
+            .lines()
+            .rev()
+            .find(|line| line.starts_with(MODEL_STRING_KEY))
+        {
+            Some(line) => line,
+            None => return Ok(None),
+        };
+        let invalid = || Error::new("invalid category encoder in model string");
+        let value: Value = serde_json::from_str(&line[MODEL_STRING_KEY.len()..])
+            .map_err(|e| Error::new(e.to_string()))?;
+        let mut encoder = Self::new();
+        for (feature, categories) in value.as_object().ok_or_else(invalid)? {
+            let categories = categories
+                .as_array()
+                .ok_or_else(invalid)?
+                .iter()
+                .map(|category| category.as_str().ok_or_else(invalid))
+                .collect::<Result<Vec<_>>>()?;
+            encoder.fit(feature, &categories);
+        }
+        Ok(Some(encoder))
+    }
+}
+
+/// Convert named raw columns to a column-major matrix, encoding categorical columns with
+/// `encoder`. Returns the values, the number of rows and the indices of the categorical
+/// columns.
+pub(crate) fn encode_columns(
+    columns: &[(&str, Column)],
+    encoder: &CategoryEncoder,
+) -> Result<(Vec<f64>, usize, Vec<usize>)> {
+    let num_rows = columns.first().map_or(0, |(_, column)| column.len());
+    if let Some((name, column)) = columns.iter().find(|(_, column)| column.len() != num_rows) {
+        return Err(Error::new(format!(
+            "column {} has {} rows, but expected {}",
+            name,
+            column.len(),
+            num_rows
+        )));
+    }
+    let mut values = Vec::with_capacity(num_rows * columns.len());
+    let mut categorical = Vec::new();
+    for (idx, (name, column)) in columns.iter().enumerate() {
+        match column {
+            Column::Numeric(column) => values.extend_from_slice(column),
+            Column::Categorical(column) => {
+                categorical.push(idx);
+                values.extend(encoder.encode(name, column)?);
+            }
+        }
+    }
+    Ok((values, num_rows, categorical))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn categorical_param() {
+        let names = ["amount", "city", "country"];
+        let categorical = vec![2.into(), "city".into()];
+        assert_eq!(
+            categorical_feature_param(&categorical, &names).unwrap(),
+            "categorical_feature=2,1"
+        );
+        assert_eq!(categorical_feature_param(&[], &names).unwrap(), "");
+        assert!(categorical_feature_param(&[3.into()], &names).is_err());
+        assert!(categorical_feature_param(&["age".into()], &names).is_err());
+    }
+
+    #[test]
+    fn encoder_codes_are_stable() {
+        let mut encoder = CategoryEncoder::new();
+        encoder.fit("city", &["Berlin", "Paris", "Berlin"]);
+        encoder.fit("city", &["Rome", "Paris"]);
+        assert_eq!(
+            encoder.categories("city").unwrap(),
+            &["Berlin", "Paris", "Rome"]
+        );
+        let codes = encoder.encode("city", &["Rome", "Oslo", "Berlin"]).unwrap();
+        assert_eq!(codes[0], 2.0);
+        assert!(codes[1].is_nan());
+        assert_eq!(codes[2], 0.0);
+        assert!(encoder.encode("country", &["DE"]).is_err());
+    }
+
+    #[test]
+    fn encoder_in_model_string() {
+        let mut encoder = CategoryEncoder::new();
+        encoder.fit("city", &["Berlin", "Paris"]);
+        encoder.fit("country", &["DE"]);
+        let mut model_string = String::from("tree\nend of parameters\n");
+        encoder.append_to_model_string(&mut model_string);
+        assert!(model_string.starts_with("tree\nend of parameters\n"));
+        assert_eq!(
+            CategoryEncoder::from_model_string(&model_string).unwrap(),
+            Some(encoder)
+        );
+        assert_eq!(CategoryEncoder::from_model_string("tree\n").unwrap(), None);
+    }
+
+    #[test]
+    fn encode_raw_columns() {
+        let mut encoder = CategoryEncoder::new();
+        encoder.fit("city", &["Berlin", "Paris"]);
+        let columns = [
+            ("amount", Column::Numeric(&[1.0, 2.0])),
+            ("city", Column::Categorical(&["Paris", "Berlin"])),
+        ];
+        let (values, num_rows, categorical) = encode_columns(&columns, &encoder).unwrap();
+        assert_eq!(values, vec![1.0, 2.0, 1.0, 0.0]);
+        assert_eq!(num_rows, 2);
+        assert_eq!(categorical, vec![1]);
+
+        let columns = [
+            ("amount", Column::Numeric(&[1.0, 2.0])),
+            ("city", Column::Categorical(&["Paris"])),
+        ];
+        assert!(encode_columns(&columns, &encoder).is_err());
+    }
+}
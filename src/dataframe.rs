@@ -0,0 +1,248 @@
+//! Conversion of polars DataFrames.
+
+use polars::prelude::*;
+
+use crate::{CategoryEncoder, DatasetParams, Error, Result};
+
+/// How null values in feature columns are treated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NullValues {
+    /// Nulls become `NaN`, which LightGBM treats as missing values.
+    Missing,
+    /// Nulls are rejected with an error.
+    Error,
+}
+
+/// Columns with special meaning when creating a [`crate::Dataset`] from a DataFrame, all other
+/// columns are features.
+///
+/// Example
+/// ```
+/// use lightgbm::{DataFrameOptions, NullValues};
+///
+/// let options = DataFrameOptions::new("label")
+///     .weight_column("weight")
+///     .group_column("query_id")
+///     .null_values(NullValues::Error);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DataFrameOptions {
+    pub(crate) label_column: String,
+    pub(crate) weight_column: Option<String>,
+    pub(crate) group_column: Option<String>,
+    pub(crate) init_score_column: Option<String>,
+    pub(crate) null_values: NullValues,
+    pub(crate) dataset_params: DatasetParams,
+}
+
+impl DataFrameOptions {
+    pub fn new(label_column: &str) -> Self {
+        Self {
+            label_column: label_column.to_string(),
+            weight_column: None,
+            group_column: None,
+            init_score_column: None,
+            null_values: NullValues::Missing,
+            dataset_params: DatasetParams::default(),
+        }
+    }
+
+    /// Column of per-row weights.
+    pub fn weight_column(mut self, column: &str) -> Self {
+        self.weight_column = Some(column.to_string());
+        self
+    }
+
+    /// Column of per-row query ids for ranking, rows of one query have to be contiguous.
+    pub fn group_column(mut self, column: &str) -> Self {
+        self.group_column = Some(column.to_string());
+        self
+    }
+
+    /// Column of initial scores, e.g. the raw predictions of another model.
+    pub fn init_score_column(mut self, column: &str) -> Self {
+        self.init_score_column = Some(column.to_string());
+        self
+    }
+
+    /// How to treat nulls in feature columns, [`NullValues::Missing`] by default.
+    pub fn null_values(mut self, null_values: NullValues) -> Self {
+        self.null_values = null_values;
+        self
+    }
+
+    /// Parameters of the dataset construction, e.g. `max_bin`.
+    pub fn dataset_params(mut self, dataset_params: DatasetParams) -> Self {
+        self.dataset_params = dataset_params;
+        self
+    }
+
+    /// Names of the columns which are not features.
+    pub(crate) fn special_columns(&self) -> Vec<&str> {
+        let mut columns = vec![self.label_column.as_str()];
+        columns.extend(
+            [
+                &self.weight_column,
+                &self.group_column,
+                &self.init_score_column,
+            ]
+            .iter()
+            .filter_map(|column| column.as_deref()),
+        );
+        columns
+    }
+}
+
+/// Feature columns of a DataFrame, converted to a column-major matrix of `f64`.
+pub(crate) struct FeatureColumns {
+    pub(crate) values: Vec<f64>,
+    pub(crate) num_rows: usize,
+    pub(crate) names: Vec<String>,
+    /// Indices of the categorical features.
+    pub(crate) categorical: Vec<usize>,
+}
+
+/// Whether a column of type `dtype` holds categories, i.e. is a categorical, enum or string
+/// column.
+fn is_categorical(dtype: &DataType) -> bool {
+    dtype.is_categorical() || dtype.is_enum() || dtype.is_string()
+}
+
+/// Add the categories of the categorical, enum and string columns among `names` to `encoder`.
+pub(crate) fn fit_categories(
+    dataframe: &DataFrame,
+    names: &[&str],
+    encoder: &mut CategoryEncoder,
+) -> Result<()> {
+    for name in names {
+        let series = dataframe.column(name)?.as_materialized_series();
+        if is_categorical(series.dtype()) {
+            let strings = series.cast(&DataType::String)?;
+            let categories = strings.str()?.into_iter().flatten().collect::<Vec<_>>();
+            encoder.fit(name, &categories);
+        }
+    }
+    Ok(())
+}
+
+/// Convert the columns `names` of `dataframe` to features.
+///
+/// Numeric and boolean columns are cast to `f64`. Categorical, enum and string columns become
+/// categorical features, whose categories are encoded with `encoder`, see
+/// [`CategoryEncoder::encode`].
+pub(crate) fn feature_columns(
+    dataframe: &DataFrame,
+    names: &[&str],
+    null_values: NullValues,
+    encoder: &CategoryEncoder,
+) -> Result<FeatureColumns> {
+    let num_rows = dataframe.height();
+    let mut values = Vec::with_capacity(num_rows * names.len());
+    let mut categorical = Vec::new();
+    for (idx, name) in names.iter().enumerate() {
+        let series = dataframe.column(name)?.as_materialized_series();
+        let dtype = series.dtype();
+        if null_values == NullValues::Error && series.null_count() > 0 {
+            return Err(Error::new(format!("column {} contains null values", name)));
+        }
+        if is_categorical(dtype) {
+            categorical.push(idx);
+            if encoder.categories(name).is_none() {
+                return Err(Error::new(format!(
+                    "feature {} is unknown to the encoder",
+                    name
+                )));
+            }
+            // nulls and unknown categories are missing values
+            let strings = series.cast(&DataType::String)?;
+            values.extend(strings.str()?.iter().map(|category| {
+                category
+                    .and_then(|category| encoder.code(name, category))
+                    .map_or(f64::NAN, f64::from)
+            }));
+        } else if dtype.is_primitive_numeric() || dtype.is_bool() {
+            let series = series.cast(&DataType::Float64)?;
+            values.extend(series.f64()?.iter().map(|value| value.unwrap_or(f64::NAN)));
+        } else {
+            return Err(Error::new(format!(
+                "column {} has unsupported type {}",
+                name, dtype
+            )));
+        }
+    }
+    Ok(FeatureColumns {
+        values,
+        num_rows,
+        names: names.iter().map(|name| name.to_string()).collect(),
+        categorical,
+    })
+}
+
+/// Read the numeric column `name` without nulls, e.g. the label.
+pub(crate) fn numeric_column<T>(dataframe: &DataFrame, name: &str) -> Result<Vec<T::Native>>
+where
+    T: PolarsNumericType,
+{
+    let series = dataframe
+        .column(name)?
+        .as_materialized_series()
+        .cast(&T::get_static_dtype())?;
+    if series.null_count() > 0 {
+        return Err(Error::new(format!("column {} contains null values", name)));
+    }
+    Ok(series.unpack::<T>()?.into_no_null_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::df;
+
+    #[test]
+    fn convert_features() {
+        let df: DataFrame = df![
+            "numeric" => [Some(1.0), None, Some(3.0)],
+            "flag" => [true, false, true],
+            "city" => ["Berlin", "Paris", "Berlin"],
+            "label" => [0.0, 1.0, 1.0]
+        ]
+        .unwrap();
+        let names = ["numeric", "flag", "city"];
+        let mut encoder = CategoryEncoder::new();
+        fit_categories(&df, &names, &mut encoder).unwrap();
+        assert_eq!(encoder.categories("city").unwrap(), &["Berlin", "Paris"]);
+        let features = feature_columns(&df, &names, NullValues::Missing, &encoder).unwrap();
+        assert_eq!(features.num_rows, 3);
+        assert_eq!(features.categorical, vec![2]);
+        assert_eq!(features.values[0], 1.0);
+        assert!(features.values[1].is_nan());
+        assert_eq!(&features.values[3..6], &[1.0, 0.0, 1.0]);
+        assert_eq!(&features.values[6..9], &[0.0, 1.0, 0.0]);
+
+        // codes come from the encoder, not from the order of the DataFrame
+        let other: DataFrame = df![
+            "city" => [Some("Paris"), Some("Rome"), None, Some("Berlin")]
+        ]
+        .unwrap();
+        let features = feature_columns(&other, &["city"], NullValues::Missing, &encoder).unwrap();
+        assert_eq!(features.values[0], 1.0);
+        assert!(features.values[1].is_nan());
+        assert!(features.values[2].is_nan());
+        assert_eq!(features.values[3], 0.0);
+
+        let empty = CategoryEncoder::new();
+        assert!(feature_columns(&df, &["city"], NullValues::Missing, &empty).is_err());
+        assert!(feature_columns(&df, &["numeric"], NullValues::Error, &empty).is_err());
+        assert!(feature_columns(&df, &["missing"], NullValues::Missing, &empty).is_err());
+        assert_eq!(
+            numeric_column::<Float32Type>(&df, "label").unwrap(),
+            vec![0.0, 1.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn special_columns() {
+        let options = DataFrameOptions::new("label").group_column("query");
+        assert_eq!(options.special_columns(), vec!["label", "query"]);
+    }
+}
@@ -1,13 +1,35 @@
 use libc::{c_char, c_void};
 use lightgbm_sys;
 use std;
+use std::collections::HashSet;
 use std::convert::TryInto;
 use std::ffi::CString;
+use std::fmt::Display;
+use std::hash::Hash;
 
+#[cfg(feature = "arrow")]
+use arrow::array::Array;
+#[cfg(feature = "arrow")]
+use arrow::record_batch::RecordBatch;
+#[cfg(feature = "ndarray")]
+use ndarray::{ArrayView1, ArrayView2};
 #[cfg(feature = "dataframe")]
 use polars::prelude::*;
+use serde_json::Value;
 
-use crate::{Error, Result};
+#[cfg(feature = "dataframe")]
+use crate::dataframe::{self, DataFrameOptions};
+
+#[cfg(feature = "arrow")]
+use crate::arrow_data::ArrowChunks;
+use crate::categorical::{categorical_feature_param, encode_columns};
+#[cfg(feature = "ndarray")]
+use crate::matrix;
+use crate::params::{params_string, resolve_aliases};
+use crate::{
+    CategoricalFeature, CategoryEncoder, Column, DatasetParams, DenseMatrix, Error, FeatureValue,
+    IndexPtr, Layout, Result,
+};
 
 /// Dataset used throughout LightGBM for training.
 ///
@@ -36,15 +58,20 @@ use crate::{Error, Result};
 /// ```
 pub struct Dataset {
     pub(crate) handle: lightgbm_sys::DatasetHandle,
+    /// Parameters the dataset was created with, see [`DatasetParams`].
+    params: String,
 }
 
 #[link(name = "c")]
 impl Dataset {
     fn new(handle: lightgbm_sys::DatasetHandle) -> Self {
-        Self { handle }
+        Self {
+            handle,
+            params: String::new(),
+        }
     }
 
-    /// Create a new `Dataset` from dense array in row-major order.
+    /// Create a new `Dataset` from dense array in row-major order, of `f32` or `f64` values.
     ///
     /// Example
     /// ```
@@ -58,45 +85,226 @@ impl Dataset {
     /// let label = vec![0.0, 0.0, 0.0, 1.0, 1.0];
     /// let dataset = Dataset::from_mat(data, label).unwrap();
     /// ```
-    pub fn from_mat(data: Vec<Vec<f64>>, label: Vec<f32>) -> Result<Self> {
+    pub fn from_mat<T: FeatureValue>(data: Vec<Vec<T>>, label: Vec<f32>) -> Result<Self> {
+        Self::from_mat_inner(data, label, std::ptr::null_mut(), "")
+    }
+
+    /// Create a new `Dataset` from dense array in row-major order, using the bin mappers
+    /// of `reference`.
+    ///
+    /// Validation data has to be created this way, with the training `Dataset` as reference.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::Dataset;
+    ///
+    /// let train = Dataset::from_mat(vec![vec![1.0, 0.1], vec![0.2, 0.8]], vec![0.0, 1.0]).unwrap();
+    /// let valid = Dataset::from_mat_with_reference(
+    ///     vec![vec![0.9, 0.2], vec![0.1, 0.7]],
+    ///     vec![0.0, 1.0],
+    ///     &train,
+    /// ).unwrap();
+    /// ```
+    pub fn from_mat_with_reference<T: FeatureValue>(
+        data: Vec<Vec<T>>,
+        label: Vec<f32>,
+        reference: &Dataset,
+    ) -> Result<Self> {
+        Self::from_mat_inner(data, label, reference.handle, "")
+    }
+
+    /// Create a new `Dataset` from dense array in row-major order with construction `params`,
+    /// using the bin mappers of `reference` if given.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::{Dataset, DatasetBuilder};
+    ///
+    /// let params = DatasetBuilder::default().max_bin(15).build().unwrap();
+    /// let data = vec![vec![1.0, 0.1], vec![0.2, 0.8]];
+    /// let dataset = Dataset::from_mat_with_params(data, vec![0.0, 1.0], &params, None).unwrap();
+    /// ```
+    pub fn from_mat_with_params<T: FeatureValue>(
+        data: Vec<Vec<T>>,
+        label: Vec<f32>,
+        params: &DatasetParams,
+        reference: Option<&Dataset>,
+    ) -> Result<Self> {
+        let params = params.to_param_string()?;
+        let mut dataset = Self::from_mat_inner(data, label, reference_handle(reference), &params)?;
+        dataset.params = params;
+        Ok(dataset)
+    }
+
+    fn from_mat_inner<T: FeatureValue>(
+        data: Vec<Vec<T>>,
+        label: Vec<f32>,
+        reference: lightgbm_sys::DatasetHandle,
+        params: &str,
+    ) -> Result<Self> {
         let data_length = data.len();
-        let feature_length = data[0].len();
-        let params = CString::new("").unwrap();
-        let label_str = CString::new("label").unwrap();
-        let reference = std::ptr::null_mut(); // not use
-        let mut handle = std::ptr::null_mut();
+        let feature_length = data.first().map_or(0, Vec::len);
+        if data.iter().any(|row| row.len() != feature_length) {
+            return Err(Error::new("all rows must have the same number of features"));
+        }
         let flat_data = data.into_iter().flatten().collect::<Vec<_>>();
+        let matrix = DenseMatrix::new(&flat_data, data_length, feature_length, Layout::RowMajor)?;
+        Self::from_dense_inner(matrix, &label, reference, params)
+    }
 
-        if data_length > i32::MAX as usize || feature_length > i32::MAX as usize {
-            return Err(Error::new(format!(
-                "received dataset of size {}x{}, but at most {}x{} is supported",
-                data_length,
-                feature_length,
-                i32::MAX,
-                i32::MAX
-            )));
+    /// Create a new `Dataset` from a contiguous dense matrix of `f32` or `f64` values, which is
+    /// passed to LightGBM without copying.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::{Dataset, DenseMatrix, Layout};
+    ///
+    /// // [[1.0, 0.1],
+    /// //  [0.7, 0.4],
+    /// //  [0.2, 0.8]]
+    /// let data: Vec<f32> = vec![1.0, 0.7, 0.2, 0.1, 0.4, 0.8];
+    /// let matrix = DenseMatrix::new(&data, 3, 2, Layout::ColumnMajor).unwrap();
+    /// let dataset = Dataset::from_dense(matrix, &[0.0, 0.0, 1.0]).unwrap();
+    /// ```
+    pub fn from_dense<T: FeatureValue>(matrix: DenseMatrix<T>, label: &[f32]) -> Result<Self> {
+        Self::from_dense_inner(matrix, label, std::ptr::null_mut(), "")
+    }
+
+    /// Create a new `Dataset` from a contiguous dense matrix, using the bin mappers of
+    /// `reference`, see [`Dataset::from_mat_with_reference`].
+    pub fn from_dense_with_reference<T: FeatureValue>(
+        matrix: DenseMatrix<T>,
+        label: &[f32],
+        reference: &Dataset,
+    ) -> Result<Self> {
+        Self::from_dense_inner(matrix, label, reference.handle, "")
+    }
+
+    /// Create a new `Dataset` from a contiguous dense matrix with construction `params`,
+    /// using the bin mappers of `reference` if given.
+    pub fn from_dense_with_params<T: FeatureValue>(
+        matrix: DenseMatrix<T>,
+        label: &[f32],
+        params: &DatasetParams,
+        reference: Option<&Dataset>,
+    ) -> Result<Self> {
+        let params = params.to_param_string()?;
+        let mut dataset =
+            Self::from_dense_inner(matrix, label, reference_handle(reference), &params)?;
+        dataset.params = params;
+        Ok(dataset)
+    }
+
+    /// Create a new `Dataset` from a contiguous dense matrix with `categorical` features,
+    /// declared by index or by name, and construction `params`. Names refer to
+    /// `feature_names`, which are set on the dataset, or to the default names `Column_0`,
+    /// `Column_1` and so on if they are `None`.
+    ///
+    /// The values of categorical features have to be non-negative integers, negative values
+    /// are treated as missing, see [`CategoryEncoder`] to encode string categories.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::{Dataset, DatasetParams, DenseMatrix, Layout};
+    ///
+    /// // [[1.0, 0.0],
+    /// //  [0.7, 2.0],
+    /// //  [0.2, 1.0]]
+    /// let data = vec![1.0, 0.0, 0.7, 2.0, 0.2, 1.0];
+    /// let matrix = DenseMatrix::new(&data, 3, 2, Layout::RowMajor).unwrap();
+    /// let dataset = Dataset::from_dense_with_categorical(
+    ///     matrix,
+    ///     &[0.0, 0.0, 1.0],
+    ///     Some(&["amount", "city"]),
+    ///     &["city".into()],
+    ///     &DatasetParams::default(),
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn from_dense_with_categorical<T: FeatureValue>(
+        matrix: DenseMatrix<T>,
+        label: &[f32],
+        feature_names: Option<&[&str]>,
+        categorical: &[CategoricalFeature],
+        params: &DatasetParams,
+    ) -> Result<Self> {
+        let names = match feature_names {
+            Some(names) => names.iter().map(|name| name.to_string()).collect(),
+            None => (0..matrix.num_cols())
+                .map(|idx| format!("Column_{}", idx))
+                .collect::<Vec<_>>(),
+        };
+        let params = params_with_categorical(params, categorical, &names)?;
+        let mut dataset = Self::from_dense_inner(matrix, label, std::ptr::null_mut(), &params)?;
+        dataset.params = params;
+        if feature_names.is_some() {
+            dataset.set_feature_names(&names)?;
+        }
+        Ok(dataset)
+    }
+
+    /// Create a new `Dataset` from named columns of numbers and string categories with
+    /// construction `params`. The string categories are added to `encoder`, which has to be
+    /// saved with the model to predict with [`crate::Booster::predict_columns`], and the
+    /// columns become categorical features.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::{CategoryEncoder, Column, Dataset, DatasetParams};
+    ///
+    /// let mut encoder = CategoryEncoder::new();
+    /// let columns = [
+    ///     ("amount", Column::Numeric(&[10.0, 7.5, 2.0])),
+    ///     ("city", Column::Categorical(&["Berlin", "Paris", "Berlin"])),
+    /// ];
+    /// let label = [0.0, 1.0, 1.0];
+    /// let params = DatasetParams::default();
+    /// let dataset = Dataset::from_columns(&columns, &label, &mut encoder, &params).unwrap();
+    /// ```
+    pub fn from_columns(
+        columns: &[(&str, Column)],
+        label: &[f32],
+        encoder: &mut CategoryEncoder,
+        params: &DatasetParams,
+    ) -> Result<Self> {
+        for (name, column) in columns {
+            if let Column::Categorical(values) = column {
+                encoder.fit(name, values);
+            }
         }
+        let (values, num_rows, categorical) = encode_columns(columns, encoder)?;
+        let names = columns.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+        let categorical = categorical
+            .into_iter()
+            .map(CategoricalFeature::Index)
+            .collect::<Vec<_>>();
+        let matrix = DenseMatrix::new(&values, num_rows, names.len(), Layout::ColumnMajor)?;
+        Self::from_dense_with_categorical(matrix, label, Some(&names), &categorical, params)
+    }
+
+    fn from_dense_inner<T: FeatureValue>(
+        matrix: DenseMatrix<T>,
+        label: &[f32],
+        reference: lightgbm_sys::DatasetHandle,
+        params: &str,
+    ) -> Result<Self> {
+        let params = CString::new(params).map_err(|e| Error::new(e.to_string()))?;
+        let mut handle = std::ptr::null_mut();
 
         lgbm_call!(lightgbm_sys::LGBM_DatasetCreateFromMat(
-            flat_data.as_ptr() as *const c_void,
-            lightgbm_sys::C_API_DTYPE_FLOAT64 as i32,
-            data_length as i32,
-            feature_length as i32,
-            1_i32,
+            matrix.data().as_ptr() as *const c_void,
+            T::DTYPE,
+            matrix.num_rows() as i32,
+            matrix.num_cols() as i32,
+            matrix.is_row_major(),
             params.as_ptr() as *const c_char,
             reference,
             &mut handle
         ))?;
 
-        lgbm_call!(lightgbm_sys::LGBM_DatasetSetField(
-            handle,
-            label_str.as_ptr() as *const c_char,
-            label.as_ptr() as *const c_void,
-            data_length as i32,
-            lightgbm_sys::C_API_DTYPE_FLOAT32 as i32
-        ))?;
-
-        Ok(Self::new(handle))
+        let mut dataset = Self::new(handle);
+        dataset.set_label(label)?;
+        Ok(dataset)
     }
 
     /// Create a new `Dataset` from file.
@@ -119,88 +327,613 @@ impl Dataset {
     /// let dataset = Dataset::from_file(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.train");
     /// ```
     pub fn from_file(file_path: &str) -> Result<Self> {
+        Self::from_file_inner(file_path, std::ptr::null_mut(), "")
+    }
+
+    /// Create a new `Dataset` from file, using the bin mappers of `reference`.
+    ///
+    /// Validation data has to be created this way, with the training `Dataset` as reference.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::Dataset;
+    ///
+    /// let train = Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train").unwrap();
+    /// let valid = Dataset::from_file_with_reference("lightgbm-sys/lightgbm/examples/binary_classification/binary.test", &train);
+    /// ```
+    pub fn from_file_with_reference(file_path: &str, reference: &Dataset) -> Result<Self> {
+        Self::from_file_inner(file_path, reference.handle, "")
+    }
+
+    /// Create a new `Dataset` from file with construction `params`, e.g. to read a CSV file
+    /// with a header, using the bin mappers of `reference` if given.
+    ///
+    /// Example
+    /// ```no_run
+    /// use lightgbm::{Dataset, DatasetBuilder};
+    ///
+    /// let params = DatasetBuilder::default()
+    ///     .header(true)
+    ///     .label_column("name:target")
+    ///     .ignore_column("name:id")
+    ///     .build()
+    ///     .unwrap();
+    /// let train = Dataset::from_file_with_params("train.csv", &params, None).unwrap();
+    /// let valid = Dataset::from_file_with_params("valid.csv", &params, Some(&train)).unwrap();
+    /// ```
+    pub fn from_file_with_params(
+        file_path: &str,
+        params: &DatasetParams,
+        reference: Option<&Dataset>,
+    ) -> Result<Self> {
+        let params = params.to_param_string()?;
+        let mut dataset = Self::from_file_inner(file_path, reference_handle(reference), &params)?;
+        dataset.params = params;
+        Ok(dataset)
+    }
+
+    fn from_file_inner(
+        file_path: &str,
+        reference: lightgbm_sys::DatasetHandle,
+        params: &str,
+    ) -> Result<Self> {
         let file_path_str = CString::new(file_path).unwrap();
-        let params = CString::new("").unwrap();
+        let params = CString::new(params).map_err(|e| Error::new(e.to_string()))?;
         let mut handle = std::ptr::null_mut();
 
         lgbm_call!(lightgbm_sys::LGBM_DatasetCreateFromFile(
             file_path_str.as_ptr() as *const c_char,
             params.as_ptr() as *const c_char,
-            std::ptr::null_mut(),
+            reference,
             &mut handle
         ))?;
 
         Ok(Self::new(handle))
     }
 
-    /// Create a new `Dataset` from a polars DataFrame.
+    /// Create a new `Dataset` from a sparse matrix in CSR (compressed sparse row) format.
     ///
-    /// Note: the feature ```dataframe``` is required for this method
+    /// Row `i` consists of the values `values[indptr[i]..indptr[i + 1]]` in the columns
+    /// `indices[indptr[i]..indptr[i + 1]]`, all other values are zero. `indptr` may be `i32`
+    /// or `i64`, `values` may be `f32` or `f64`.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::Dataset;
+    ///
+    /// // [[1.0, 0.0, 0.0],
+    /// //  [0.0, 0.0, 2.0],
+    /// //  [0.0, 3.0, 4.0]]
+    /// let indptr: Vec<i32> = vec![0, 1, 2, 4];
+    /// let indices = vec![0, 2, 1, 2];
+    /// let values: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0];
+    /// let label = vec![0.0, 1.0, 1.0];
+    /// let dataset = Dataset::from_csr(&indptr, &indices, &values, 3, label).unwrap();
+    /// ```
+    pub fn from_csr<P: IndexPtr, T: FeatureValue>(
+        indptr: &[P],
+        indices: &[i32],
+        values: &[T],
+        num_col: usize,
+        label: Vec<f32>,
+    ) -> Result<Self> {
+        Self::from_csr_inner(
+            indptr,
+            indices,
+            values,
+            num_col,
+            &label,
+            std::ptr::null_mut(),
+            "",
+        )
+    }
+
+    /// Create a new `Dataset` from a sparse matrix in CSR format, using the bin mappers of
+    /// `reference`, see [`Dataset::from_mat_with_reference`].
+    pub fn from_csr_with_reference<P: IndexPtr, T: FeatureValue>(
+        indptr: &[P],
+        indices: &[i32],
+        values: &[T],
+        num_col: usize,
+        label: Vec<f32>,
+        reference: &Dataset,
+    ) -> Result<Self> {
+        Self::from_csr_inner(
+            indptr,
+            indices,
+            values,
+            num_col,
+            &label,
+            reference.handle,
+            "",
+        )
+    }
+
+    /// Create a new `Dataset` from a sparse matrix in CSR format with construction `params`,
+    /// using the bin mappers of `reference` if given, see [`Dataset::from_csr`].
+    pub fn from_csr_with_params<P: IndexPtr, T: FeatureValue>(
+        indptr: &[P],
+        indices: &[i32],
+        values: &[T],
+        num_col: usize,
+        label: Vec<f32>,
+        params: &DatasetParams,
+        reference: Option<&Dataset>,
+    ) -> Result<Self> {
+        let params = params.to_param_string()?;
+        let mut dataset = Self::from_csr_inner(
+            indptr,
+            indices,
+            values,
+            num_col,
+            &label,
+            reference_handle(reference),
+            &params,
+        )?;
+        dataset.params = params;
+        Ok(dataset)
+    }
+
+    fn from_csr_inner<P: IndexPtr, T: FeatureValue>(
+        indptr: &[P],
+        indices: &[i32],
+        values: &[T],
+        num_col: usize,
+        label: &[f32],
+        reference: lightgbm_sys::DatasetHandle,
+        params: &str,
+    ) -> Result<Self> {
+        check_compressed(indptr, indices, values, num_col)?;
+        let params = CString::new(params).map_err(|e| Error::new(e.to_string()))?;
+        let mut handle = std::ptr::null_mut();
+
+        lgbm_call!(lightgbm_sys::LGBM_DatasetCreateFromCSR(
+            indptr.as_ptr() as *const c_void,
+            P::DTYPE,
+            indices.as_ptr(),
+            values.as_ptr() as *const c_void,
+            T::DTYPE,
+            indptr.len() as i64,
+            values.len() as i64,
+            num_col as i64,
+            params.as_ptr() as *const c_char,
+            reference,
+            &mut handle
+        ))?;
+
+        let mut dataset = Self::new(handle);
+        dataset.set_label(label)?;
+        Ok(dataset)
+    }
+
+    /// Create a new `Dataset` from a sparse matrix in CSC (compressed sparse column) format.
+    ///
+    /// Column `j` consists of the values `values[col_ptr[j]..col_ptr[j + 1]]` in the rows
+    /// `row_indices[col_ptr[j]..col_ptr[j + 1]]`, all other values are zero. `col_ptr` may be
+    /// `i32` or `i64`, `values` may be `f32` or `f64`.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::Dataset;
+    ///
+    /// // [[1.0, 0.0, 0.0],
+    /// //  [0.0, 0.0, 2.0],
+    /// //  [0.0, 3.0, 4.0]]
+    /// let col_ptr: Vec<i64> = vec![0, 1, 2, 4];
+    /// let row_indices = vec![0, 2, 1, 2];
+    /// let values: Vec<f64> = vec![1.0, 3.0, 2.0, 4.0];
+    /// let label = vec![0.0, 1.0, 1.0];
+    /// let dataset = Dataset::from_csc(&col_ptr, &row_indices, &values, 3, label).unwrap();
+    /// ```
+    pub fn from_csc<P: IndexPtr, T: FeatureValue>(
+        col_ptr: &[P],
+        row_indices: &[i32],
+        values: &[T],
+        num_row: usize,
+        label: Vec<f32>,
+    ) -> Result<Self> {
+        Self::from_csc_inner(
+            col_ptr,
+            row_indices,
+            values,
+            num_row,
+            &label,
+            std::ptr::null_mut(),
+            "",
+        )
+    }
+
+    /// Create a new `Dataset` from a sparse matrix in CSC format, using the bin mappers of
+    /// `reference`, see [`Dataset::from_mat_with_reference`].
+    pub fn from_csc_with_reference<P: IndexPtr, T: FeatureValue>(
+        col_ptr: &[P],
+        row_indices: &[i32],
+        values: &[T],
+        num_row: usize,
+        label: Vec<f32>,
+        reference: &Dataset,
+    ) -> Result<Self> {
+        Self::from_csc_inner(
+            col_ptr,
+            row_indices,
+            values,
+            num_row,
+            &label,
+            reference.handle,
+            "",
+        )
+    }
+
+    /// Create a new `Dataset` from a sparse matrix in CSC format with construction `params`,
+    /// using the bin mappers of `reference` if given, see [`Dataset::from_csc`].
+    pub fn from_csc_with_params<P: IndexPtr, T: FeatureValue>(
+        col_ptr: &[P],
+        row_indices: &[i32],
+        values: &[T],
+        num_row: usize,
+        label: Vec<f32>,
+        params: &DatasetParams,
+        reference: Option<&Dataset>,
+    ) -> Result<Self> {
+        let params = params.to_param_string()?;
+        let mut dataset = Self::from_csc_inner(
+            col_ptr,
+            row_indices,
+            values,
+            num_row,
+            &label,
+            reference_handle(reference),
+            &params,
+        )?;
+        dataset.params = params;
+        Ok(dataset)
+    }
+
+    fn from_csc_inner<P: IndexPtr, T: FeatureValue>(
+        col_ptr: &[P],
+        row_indices: &[i32],
+        values: &[T],
+        num_row: usize,
+        label: &[f32],
+        reference: lightgbm_sys::DatasetHandle,
+        params: &str,
+    ) -> Result<Self> {
+        check_compressed(col_ptr, row_indices, values, num_row)?;
+        let params = CString::new(params).map_err(|e| Error::new(e.to_string()))?;
+        let mut handle = std::ptr::null_mut();
+
+        lgbm_call!(lightgbm_sys::LGBM_DatasetCreateFromCSC(
+            col_ptr.as_ptr() as *const c_void,
+            P::DTYPE,
+            row_indices.as_ptr(),
+            values.as_ptr() as *const c_void,
+            T::DTYPE,
+            col_ptr.len() as i64,
+            values.len() as i64,
+            num_row as i64,
+            params.as_ptr() as *const c_char,
+            reference,
+            &mut handle
+        ))?;
+
+        let mut dataset = Self::new(handle);
+        dataset.set_label(label)?;
+        Ok(dataset)
+    }
+
+    /// Create a new `Dataset` from an ndarray matrix of `f32` or `f64` values.
+    ///
+    /// The memory of `data` is passed to LightGBM directly if it is standard (row-major) or
+    /// Fortran (column-major) contiguous, other views are copied.
+    ///
+    /// Note: the feature ```ndarray``` is required for this method
     ///
     /// Example
     ///
     #[cfg_attr(
-        feature = "dataframe",
+        feature = "ndarray",
         doc = r##"
-    extern crate polars;
+```
+extern crate ndarray;
 
-    use lightgbm::Dataset;
-    use polars::prelude::*;
-    use polars::df;
+use lightgbm::Dataset;
+use ndarray::{array, Array1};
 
-    let df: DataFrame = df![
-            "feature_1" => [1.0, 0.7, 0.9, 0.2, 0.1],
-            "feature_2" => [0.1, 0.4, 0.8, 0.2, 0.7],
-            "feature_3" => [0.2, 0.5, 0.5, 0.1, 0.1],
-            "feature_4" => [0.1, 0.1, 0.1, 0.7, 0.9],
-            "label" => [0.0, 0.0, 0.0, 1.0, 1.0]
-        ].unwrap();
-    let dataset = Dataset::from_dataframe(df, String::from("label")).unwrap();
-    "##
+let data = array![[1.0, 0.1, 0.2], [0.7, 0.4, 0.5], [0.2, 0.2, 0.8]];
+let label: Array1<f32> = array![0.0, 0.0, 1.0];
+let dataset = Dataset::from_array(data.view(), label.view()).unwrap();
+```
+"##
     )]
-    #[cfg(feature = "dataframe")]
-    pub fn from_dataframe(mut dataframe: DataFrame, label_column: String) -> Result<Self> {
-        let label_col_name = label_column.as_str();
+    #[cfg(feature = "ndarray")]
+    pub fn from_array<T: FeatureValue>(
+        data: ArrayView2<T>,
+        label: ArrayView1<f32>,
+    ) -> Result<Self> {
+        let label = label.to_vec();
+        matrix::with_array_view(data, |matrix| Self::from_dense(matrix, &label))
+    }
 
-        let (m, n) = dataframe.shape();
+    /// Create a new `Dataset` from arrow record batches, every column being one feature, and
+    /// a numeric `label` array. The batches are chunks of one table and must share a schema.
+    ///
+    /// The data is passed to LightGBM through the Arrow C data interface without copying.
+    ///
+    /// Note: the feature ```arrow``` is required for this method
+    ///
+    /// Example
+    ///
+    #[cfg_attr(
+        feature = "arrow",
+        doc = r##"
+```
+extern crate arrow;
 
-        let label_series = &dataframe.select_series(label_col_name)?[0].cast::<Float32Type>()?;
+use std::sync::Arc;
 
-        if label_series.null_count() != 0 {
-            panic!("Cannot create a dataset with null values, encountered nulls when creating the label array")
-        }
+use arrow::array::{ArrayRef, Float32Array, Float64Array};
+use arrow::record_batch::RecordBatch;
+use lightgbm::Dataset;
+
+let batch = RecordBatch::try_from_iter(vec![
+    ("feature_1", Arc::new(Float64Array::from(vec![1.0, 0.7, 0.2])) as ArrayRef),
+    ("feature_2", Arc::new(Float32Array::from(vec![0.1, 0.4, 0.8])) as ArrayRef),
+])
+.unwrap();
+let label = Float32Array::from(vec![0.0, 0.0, 1.0]);
+let dataset = Dataset::from_arrow(&[batch], &label).unwrap();
+```
+"##
+    )]
+    #[cfg(feature = "arrow")]
+    pub fn from_arrow(batches: &[RecordBatch], label: &dyn Array) -> Result<Self> {
+        Self::from_arrow_inner(batches, label, std::ptr::null_mut(), "")
+    }
 
-        dataframe.drop_in_place(label_col_name)?;
+    /// Create a new `Dataset` from arrow record batches, using the bin mappers of
+    /// `reference`, see [`Dataset::from_mat_with_reference`].
+    ///
+    /// Note: the feature ```arrow``` is required for this method
+    #[cfg(feature = "arrow")]
+    pub fn from_arrow_with_reference(
+        batches: &[RecordBatch],
+        label: &dyn Array,
+        reference: &Dataset,
+    ) -> Result<Self> {
+        Self::from_arrow_inner(batches, label, reference.handle, "")
+    }
 
-        let mut label_values = Vec::with_capacity(m);
+    /// Create a new `Dataset` from arrow record batches with construction `params`, using the
+    /// bin mappers of `reference` if given, see [`Dataset::from_arrow`].
+    ///
+    /// Note: the feature ```arrow``` is required for this method
+    #[cfg(feature = "arrow")]
+    pub fn from_arrow_with_params(
+        batches: &[RecordBatch],
+        label: &dyn Array,
+        params: &DatasetParams,
+        reference: Option<&Dataset>,
+    ) -> Result<Self> {
+        let params = params.to_param_string()?;
+        let mut dataset =
+            Self::from_arrow_inner(batches, label, reference_handle(reference), &params)?;
+        dataset.params = params;
+        Ok(dataset)
+    }
 
-        let label_values_ca = label_series.unpack::<Float32Type>()?;
+    #[cfg(feature = "arrow")]
+    fn from_arrow_inner(
+        batches: &[RecordBatch],
+        label: &dyn Array,
+        reference: lightgbm_sys::DatasetHandle,
+        params: &str,
+    ) -> Result<Self> {
+        let chunks = ArrowChunks::from_batches(batches)?;
+        let params = CString::new(params).map_err(|e| Error::new(e.to_string()))?;
+        let mut handle = std::ptr::null_mut();
+
+        lgbm_call!(lightgbm_sys::LGBM_DatasetCreateFromArrow(
+            chunks.num_chunks(),
+            chunks.chunks(),
+            chunks.schema(),
+            params.as_ptr() as *const c_char,
+            reference,
+            &mut handle
+        ))?;
 
-        label_values_ca
-            .into_no_null_iter()
-            .enumerate()
-            .for_each(|(_row_idx, val)| {
-                label_values.push(val);
-            });
+        let mut dataset = Self::new(handle);
+        dataset.set_field_from_arrow("label", label)?;
+        Ok(dataset)
+    }
 
-        let mut feature_values = Vec::with_capacity(m);
-        for _i in 0..m {
-            feature_values.push(Vec::with_capacity(n));
+    /// Set the field `field_name` (`label`, `weight`, `init_score` or `group`) from a
+    /// numeric arrow array.
+    ///
+    /// Note: the feature ```arrow``` is required for this method
+    #[cfg(feature = "arrow")]
+    pub fn set_field_from_arrow(&mut self, field_name: &str, array: &dyn Array) -> Result<()> {
+        if array.null_count() > 0 {
+            return Err(Error::new(format!(
+                "{} must not contain null values",
+                field_name
+            )));
         }
+        let chunks = ArrowChunks::from_array(array)?;
+        let field_name = CString::new(field_name).map_err(|e| Error::new(e.to_string()))?;
+        lgbm_call!(lightgbm_sys::LGBM_DatasetSetFieldFromArrow(
+            self.handle,
+            field_name.as_ptr() as *const c_char,
+            chunks.num_chunks(),
+            chunks.chunks(),
+            chunks.schema()
+        ))?;
+        Ok(())
+    }
 
-        for (_col_idx, series) in dataframe.get_columns().iter().enumerate() {
-            if series.null_count() != 0 {
-                panic!("Cannot create a dataset with null values, encountered nulls when creating the features array")
-            }
+    /// Create a new `Dataset` from a polars DataFrame, all columns but `label_column` being
+    /// features, see [`Dataset::from_dataframe_with_options`].
+    ///
+    /// Categorical, enum and string columns are rejected, as their [`CategoryEncoder`] is
+    /// needed for prediction, use [`Dataset::from_dataframe_with_options`] for them.
+    ///
+    /// Note: the feature ```dataframe``` is required for this method
+    ///
+    /// Example
+    ///
+    #[cfg_attr(
+        feature = "dataframe",
+        doc = r##"
+```
+extern crate polars;
+
+use lightgbm::Dataset;
+use polars::prelude::*;
+use polars::df;
+
+let df: DataFrame = df![
+        "feature_1" => [1.0, 0.7, 0.9, 0.2, 0.1],
+        "feature_2" => [0.1, 0.4, 0.8, 0.2, 0.7],
+        "feature_3" => [0.2, 0.5, 0.5, 0.1, 0.1],
+        "feature_4" => [0.1, 0.1, 0.1, 0.7, 0.9],
+        "label" => [0.0, 0.0, 0.0, 1.0, 1.0]
+    ].unwrap();
+let dataset = Dataset::from_dataframe(df, String::from("label")).unwrap();
+```
+"##
+    )]
+    #[cfg(feature = "dataframe")]
+    pub fn from_dataframe(dataframe: DataFrame, label_column: String) -> Result<Self> {
+        let mut encoder = CategoryEncoder::new();
+        let dataset = Self::from_dataframe_with_options(
+            &dataframe,
+            &DataFrameOptions::new(&label_column),
+            &mut encoder,
+        )?;
+        if let Some(feature) = encoder.features().next() {
+            return Err(Error::new(format!(
+                "column {} holds categories, use from_dataframe_with_options to keep their encoder",
+                feature
+            )));
+        }
+        Ok(dataset)
+    }
+
+    /// Create a new `Dataset` from a polars DataFrame.
+    ///
+    /// The columns named in `options` hold the label and optionally weights, query ids and
+    /// initial scores, all other columns are features named after the column. Numeric and
+    /// boolean columns are used as they are, nulls become missing values unless
+    /// [`crate::NullValues::Error`] is set. Categorical, enum and string columns become
+    /// categorical features, whose categories are added to `encoder` like in
+    /// [`Dataset::from_columns`]. The encoder has to be saved with the model, see
+    /// [`crate::Booster::save_string_with_encoder`], to predict with
+    /// [`crate::Booster::predict_dataframe`].
+    ///
+    /// Note: the feature ```dataframe``` is required for this method
+    ///
+    /// Example
+    ///
+    #[cfg_attr(
+        feature = "dataframe",
+        doc = r##"
+```
+extern crate polars;
 
-            let series = series.cast::<Float64Type>()?;
-            let ca = series.unpack::<Float64Type>()?;
+use lightgbm::{CategoryEncoder, DataFrameOptions, Dataset};
+use polars::prelude::*;
+use polars::df;
+
+let df: DataFrame = df![
+        "amount" => [Some(10.0), Some(7.5), None, Some(2.0)],
+        "country" => ["DE", "FR", "DE", "US"],
+        "weight" => [1.0, 1.0, 2.0, 1.0],
+        "label" => [0.0, 0.0, 1.0, 1.0]
+    ].unwrap();
+let options = DataFrameOptions::new("label").weight_column("weight");
+let mut encoder = CategoryEncoder::new();
+let dataset = Dataset::from_dataframe_with_options(&df, &options, &mut encoder).unwrap();
+```
+"##
+    )]
+    #[cfg(feature = "dataframe")]
+    pub fn from_dataframe_with_options(
+        dataframe: &DataFrame,
+        options: &DataFrameOptions,
+        encoder: &mut CategoryEncoder,
+    ) -> Result<Self> {
+        let special_columns = options.special_columns();
+        let feature_names = dataframe
+            .get_column_names_str()
+            .into_iter()
+            .filter(|name| !special_columns.contains(name))
+            .collect::<Vec<_>>();
+        dataframe::fit_categories(dataframe, &feature_names, encoder)?;
+        let features =
+            dataframe::feature_columns(dataframe, &feature_names, options.null_values, encoder)?;
+        let label = dataframe::numeric_column::<Float32Type>(dataframe, &options.label_column)?;
 
-            ca.into_no_null_iter()
-                .enumerate()
-                .for_each(|(row_idx, val)| feature_values[row_idx].push(val));
+        let matrix = DenseMatrix::new(
+            &features.values,
+            features.num_rows,
+            features.names.len(),
+            Layout::ColumnMajor,
+        )?;
+        let categorical = features
+            .categorical
+            .iter()
+            .map(|idx| CategoricalFeature::Index(*idx))
+            .collect::<Vec<_>>();
+        let params =
+            params_with_categorical(&options.dataset_params, &categorical, &features.names)?;
+        let mut dataset = Self::from_dense_inner(matrix, &label, std::ptr::null_mut(), &params)?;
+        dataset.params = params;
+        dataset.set_feature_names(&features.names)?;
+
+        if let Some(column) = &options.weight_column {
+            dataset.set_weights(dataframe::numeric_column::<Float32Type>(dataframe, column)?)?;
+        }
+        if let Some(column) = &options.group_column {
+            let query_ids = dataframe::numeric_column::<Int64Type>(dataframe, column)?;
+            dataset.set_groups_from_query_ids(&query_ids)?;
+        }
+        if let Some(column) = &options.init_score_column {
+            let init_score = dataframe::numeric_column::<Float64Type>(dataframe, column)?;
+            dataset.set_init_score(&init_score)?;
         }
-        Self::from_mat(feature_values, label_values)
+        Ok(dataset)
+    }
+
+    /// Check that the training parameters `parameter` don't change any of the parameters the
+    /// dataset was created with, e.g. `max_bin`, which LightGBM would otherwise ignore. Done
+    /// when training a [`crate::Booster`] on a dataset created with [`DatasetParams`].
+    ///
+    /// Example
+    /// ```
+    /// extern crate serde_json;
+    /// use lightgbm::{Dataset, DatasetBuilder};
+    /// use serde_json::json;
+    ///
+    /// let params = DatasetBuilder::default().max_bin(15).build().unwrap();
+    /// let data = vec![vec![1.0, 0.1], vec![0.2, 0.8]];
+    /// let dataset = Dataset::from_mat_with_params(data, vec![0.0, 1.0], &params, None).unwrap();
+    /// assert!(dataset.check_train_params(&json!{{"max_bin": 15, "num_leaves": 7}}).is_ok());
+    /// assert!(dataset.check_train_params(&json!{{"max_bin": 255}}).is_err());
+    /// ```
+    pub fn check_train_params(&self, parameter: &Value) -> Result<()> {
+        let old_params = CString::new(self.params.as_str()).unwrap();
+        let new_params = CString::new(params_string(&resolve_aliases(parameter)?)?)
+            .map_err(|e| Error::new(e.to_string()))?;
+        lgbm_call!(lightgbm_sys::LGBM_DatasetUpdateParamChecking(
+            old_params.as_ptr() as *const c_char,
+            new_params.as_ptr() as *const c_char
+        ))?;
+        Ok(())
+    }
+
+    /// Whether the dataset was created with [`DatasetParams`], which training parameters are
+    /// checked against.
+    pub(crate) fn has_params(&self) -> bool {
+        !self.params.is_empty()
     }
 
     pub fn get_data_len(&self) -> Result<usize> {
@@ -225,26 +958,441 @@ impl Dataset {
             .map_err(|_| Error::new("feature count negative"))
     }
 
-    pub fn set_weights(&mut self, weights: Vec<f32>) -> Result<()> {
-        let dataset_len = self.get_data_len()?;
-        if dataset_len != weights.len() {
+    /// Set the names of the features, one per feature, which are saved with models trained on
+    /// the dataset instead of `Column_0`, `Column_1` and so on.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::Dataset;
+    ///
+    /// let data = vec![vec![1.0, 0.1],
+    ///                 vec![0.7, 0.4],
+    ///                 vec![0.9, 0.8]];
+    /// let label = vec![0.0, 1.0, 1.0];
+    /// let mut dataset = Dataset::from_mat(data, label).unwrap();
+    /// dataset.set_feature_names(&["amount", "age"]).unwrap();
+    /// assert_eq!(dataset.feature_names().unwrap(), vec!["amount", "age"]);
+    /// ```
+    pub fn set_feature_names<S: AsRef<str>>(&mut self, names: &[S]) -> Result<()> {
+        let num_feature = self.get_feature_count()?;
+        if names.len() != num_feature {
             return Err(Error::new(format!(
-                "got {} weights, but dataset has {} records",
-                weights.len(),
-                dataset_len
+                "got {} feature names, but dataset has {} features",
+                names.len(),
+                num_feature
             )));
         }
-        let field_name = CString::new("weight").unwrap();
-        let len = weights.len();
+        let names = c_strings(names)?;
+        let mut name_ptrs = names
+            .iter()
+            .map(|name| name.as_ptr())
+            .collect::<Vec<*const c_char>>();
+        lgbm_call!(lightgbm_sys::LGBM_DatasetSetFeatureNames(
+            self.handle,
+            name_ptrs.as_mut_ptr(),
+            name_ptrs.len() as i32
+        ))?;
+        Ok(())
+    }
+
+    /// Get the names of the features, `Column_0`, `Column_1` and so on if they were not set.
+    pub fn feature_names(&self) -> Result<Vec<String>> {
+        let num_feature = self.get_feature_count()?;
+        read_names(num_feature, |buffer_len, out_buffer_len, out_strs| {
+            let mut num_feature_names = 0;
+            lgbm_call!(lightgbm_sys::LGBM_DatasetGetFeatureNames(
+                self.handle,
+                num_feature as i32,
+                &mut num_feature_names,
+                buffer_len,
+                out_buffer_len,
+                out_strs
+            ))
+        })
+    }
+
+    /// Set the field `field_name` to `len` values of type `dtype` at `data`.
+    fn set_field_raw(
+        &mut self,
+        field_name: &str,
+        data: *const c_void,
+        len: usize,
+        dtype: i32,
+    ) -> Result<()> {
+        let field_name = CString::new(field_name).unwrap();
         lgbm_call!(lightgbm_sys::LGBM_DatasetSetField(
             self.handle,
             field_name.as_ptr() as *const c_char,
-            weights.as_ptr() as *const c_void,
+            data,
             len as i32,
+            dtype
+        ))?;
+        Ok(())
+    }
+
+    /// Set the labels, one per row.
+    pub fn set_label(&mut self, label: &[f32]) -> Result<()> {
+        self.check_num_records("labels", label.len())?;
+        self.set_field_raw(
+            "label",
+            label.as_ptr() as *const c_void,
+            label.len(),
             lightgbm_sys::C_API_DTYPE_FLOAT32 as i32,
+        )
+    }
+
+    /// Get the labels of the dataset.
+    ///
+    /// Example
+    /// ```no_run
+    /// use lightgbm::Dataset;
+    ///
+    /// let dataset = Dataset::from_file(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.train").unwrap();
+    /// let num_positive = dataset.get_label().unwrap().iter().filter(|label| **label > 0.0).count();
+    /// ```
+    pub fn get_label(&self) -> Result<Vec<f32>> {
+        self.get_field("label", lightgbm_sys::C_API_DTYPE_FLOAT32)
+    }
+
+    pub fn set_weights(&mut self, weights: Vec<f32>) -> Result<()> {
+        self.check_num_records("weights", weights.len())?;
+        self.set_field_raw(
+            "weight",
+            weights.as_ptr() as *const c_void,
+            weights.len(),
+            lightgbm_sys::C_API_DTYPE_FLOAT32 as i32,
+        )
+    }
+
+    /// Get the weights of the dataset, `None` if no weights are set.
+    pub fn get_weights(&self) -> Result<Option<Vec<f32>>> {
+        let weights = self.get_field("weight", lightgbm_sys::C_API_DTYPE_FLOAT32)?;
+        Ok(non_empty(weights))
+    }
+
+    /// Set the initial scores, e.g. the raw predictions of a baseline model, which boosting
+    /// starts from instead of the average label.
+    ///
+    /// Models with several outputs, like `multiclass`, take one score per row and class, stored
+    /// class by class: first the scores of all rows for class 0, then for class 1 and so on.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::Dataset;
+    ///
+    /// let data = vec![vec![1.0, 0.1],
+    ///                 vec![0.7, 0.4],
+    ///                 vec![0.9, 0.8]];
+    /// let label = vec![0.0, 2.0, 1.0];
+    /// let mut dataset = Dataset::from_mat(data, label).unwrap();
+    /// // 3 classes: the scores of all rows for class 0, then class 1, then class 2
+    /// let init_score = vec![0.5, 0.1, 0.2, 0.3, 0.4, 0.6, 0.2, 0.5, 0.2];
+    /// dataset.set_init_score(&init_score).unwrap();
+    /// ```
+    pub fn set_init_score(&mut self, init_score: &[f64]) -> Result<()> {
+        let dataset_len = self.get_data_len()?;
+        let num_class = init_score.len() / dataset_len.max(1);
+        if num_class == 0 || num_class * dataset_len != init_score.len() {
+            return Err(Error::new(format!(
+                "got {} initial scores, but need a multiple of the {} records of the dataset",
+                init_score.len(),
+                dataset_len
+            )));
+        }
+        self.set_field_raw(
+            "init_score",
+            init_score.as_ptr() as *const c_void,
+            init_score.len(),
+            lightgbm_sys::C_API_DTYPE_FLOAT64 as i32,
+        )
+    }
+
+    /// Get the initial scores in the layout of [`Dataset::set_init_score`], `None` if they are
+    /// not set.
+    pub fn get_init_score(&self) -> Result<Option<Vec<f64>>> {
+        let init_score = self.get_field("init_score", lightgbm_sys::C_API_DTYPE_FLOAT64)?;
+        Ok(non_empty(init_score))
+    }
+
+    /// Get the number of rows of every query, see [`Dataset::set_groups`], `None` if no groups
+    /// are set.
+    pub fn get_groups(&self) -> Result<Option<Vec<i32>>> {
+        // LightGBM stores the boundaries of the queries, starting with 0
+        let boundaries: Vec<i32> = self.get_field("group", lightgbm_sys::C_API_DTYPE_INT32)?;
+        Ok(non_empty(
+            boundaries.windows(2).map(|w| w[1] - w[0]).collect(),
+        ))
+    }
+
+    /// Get the positions of the rows, see [`Dataset::set_positions`], `None` if they are not
+    /// set.
+    pub fn get_positions(&self) -> Result<Option<Vec<i32>>> {
+        let positions = self.get_field("position", lightgbm_sys::C_API_DTYPE_INT32)?;
+        Ok(non_empty(positions))
+    }
+
+    /// Read the field `field_name` of type `dtype`, empty if it is not set.
+    fn get_field<T: Copy>(&self, field_name: &str, dtype: u32) -> Result<Vec<T>> {
+        let c_field_name = CString::new(field_name).unwrap();
+        let mut out_len = 0_i32;
+        let mut out_ptr = std::ptr::null();
+        let mut out_type = 0_i32;
+        lgbm_call!(lightgbm_sys::LGBM_DatasetGetField(
+            self.handle,
+            c_field_name.as_ptr() as *const c_char,
+            &mut out_len,
+            &mut out_ptr,
+            &mut out_type
         ))?;
+        if out_type != dtype as i32 {
+            return Err(Error::new(format!(
+                "field {} has unexpected type {}",
+                field_name, out_type
+            )));
+        }
+        if out_ptr.is_null() || out_len <= 0 {
+            return Ok(Vec::new());
+        }
+        // the pointer refers to memory owned by the dataset, so copy it
+        let values = unsafe { std::slice::from_raw_parts(out_ptr as *const T, out_len as usize) };
+        Ok(values.to_vec())
+    }
+
+    /// Check that `len` values of `what` match the number of rows.
+    fn check_num_records(&self, what: &str, len: usize) -> Result<()> {
+        let dataset_len = self.get_data_len()?;
+        if dataset_len != len {
+            return Err(Error::new(format!(
+                "got {} {}, but dataset has {} records",
+                len, what, dataset_len
+            )));
+        }
         Ok(())
     }
+
+    /// Set the query groups for ranking objectives like `lambdarank`, given as the number of
+    /// rows of every query. The rows of one query have to be contiguous, so the sizes have to
+    /// sum up to the number of rows.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::Dataset;
+    ///
+    /// let data = vec![vec![1.0, 0.1],
+    ///                 vec![0.7, 0.4],
+    ///                 vec![0.9, 0.8],
+    ///                 vec![0.2, 0.2],
+    ///                 vec![0.1, 0.7]];
+    /// let relevance = vec![0.0, 2.0, 1.0, 1.0, 0.0];
+    /// let mut dataset = Dataset::from_mat(data, relevance).unwrap();
+    /// // the first three rows belong to one query, the last two to another
+    /// dataset.set_groups(&[3, 2]).unwrap();
+    /// ```
+    pub fn set_groups(&mut self, group_sizes: &[i32]) -> Result<()> {
+        let dataset_len = self.get_data_len()?;
+        if group_sizes.iter().any(|size| *size <= 0) {
+            return Err(Error::new("group sizes have to be positive"));
+        }
+        let num_rows: i64 = group_sizes.iter().map(|size| i64::from(*size)).sum();
+        if num_rows != dataset_len as i64 {
+            return Err(Error::new(format!(
+                "groups contain {} rows, but dataset has {} records",
+                num_rows, dataset_len
+            )));
+        }
+        self.set_field_raw(
+            "group",
+            group_sizes.as_ptr() as *const c_void,
+            group_sizes.len(),
+            lightgbm_sys::C_API_DTYPE_INT32 as i32,
+        )
+    }
+
+    /// Set the query groups from the query id of every row, see [`Dataset::set_groups`]. The
+    /// rows of one query have to be contiguous.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::Dataset;
+    ///
+    /// let data = vec![vec![1.0, 0.1],
+    ///                 vec![0.7, 0.4],
+    ///                 vec![0.9, 0.8],
+    ///                 vec![0.2, 0.2],
+    ///                 vec![0.1, 0.7]];
+    /// let relevance = vec![0.0, 2.0, 1.0, 1.0, 0.0];
+    /// let mut dataset = Dataset::from_mat(data, relevance).unwrap();
+    /// dataset.set_groups_from_query_ids(&["q1", "q1", "q1", "q2", "q2"]).unwrap();
+    /// ```
+    pub fn set_groups_from_query_ids<Q: Eq + Hash + Display>(
+        &mut self,
+        query_ids: &[Q],
+    ) -> Result<()> {
+        self.check_num_records("query ids", query_ids.len())?;
+        self.set_groups(&group_sizes(query_ids)?)
+    }
+
+    /// Set the position at which every row was displayed, which lets `lambdarank` learn and
+    /// remove the position bias of the labels (unbiased learning to rank).
+    pub fn set_positions(&mut self, positions: &[i32]) -> Result<()> {
+        self.check_num_records("positions", positions.len())?;
+        self.set_field_raw(
+            "position",
+            positions.as_ptr() as *const c_void,
+            positions.len(),
+            lightgbm_sys::C_API_DTYPE_INT32 as i32,
+        )
+    }
+}
+
+/// The handle of an optional reference dataset, null if there is none.
+/// The construction `params` together with the `categorical_feature` parameter declaring
+/// `categorical` features, whose names are resolved against `feature_names`.
+fn params_with_categorical<S: AsRef<str>>(
+    params: &DatasetParams,
+    categorical: &[CategoricalFeature],
+    feature_names: &[S],
+) -> Result<String> {
+    let categorical = categorical_feature_param(categorical, feature_names)?;
+    let params = params.to_param_string()?;
+    if categorical.is_empty() {
+        return Ok(params);
+    }
+    if params
+        .split(' ')
+        .any(|param| param.starts_with("categorical_feature="))
+    {
+        return Err(Error::new(
+            "categorical features are declared both directly and in the dataset parameters",
+        ));
+    }
+    if params.is_empty() {
+        Ok(categorical)
+    } else {
+        Ok(format!("{} {}", categorical, params))
+    }
+}
+
+fn reference_handle(reference: Option<&Dataset>) -> lightgbm_sys::DatasetHandle {
+    reference.map_or(std::ptr::null_mut(), |reference| reference.handle)
+}
+
+/// Convert names to C strings, failing for names containing a nul byte.
+pub(crate) fn c_strings<S: AsRef<str>>(names: &[S]) -> Result<Vec<CString>> {
+    names
+        .iter()
+        .map(|name| CString::new(name.as_ref()).map_err(|e| Error::new(e.to_string())))
+        .collect()
+}
+
+/// Read `num_names` strings with one of LightGBM's `Get*Names` functions, which is called
+/// with the length of every buffer, a pointer receiving the required length and the buffers.
+/// The buffers are enlarged and the call repeated if a name does not fit.
+pub(crate) fn read_names<F>(num_names: usize, mut get_names: F) -> Result<Vec<String>>
+where
+    F: FnMut(usize, &mut usize, *mut *mut c_char) -> Result<()>,
+{
+    let mut buffer_len = 64;
+    loop {
+        let mut buffers = vec![vec![0_u8; buffer_len]; num_names];
+        let mut ptrs = buffers
+            .iter_mut()
+            .map(|buffer| buffer.as_mut_ptr() as *mut c_char)
+            .collect::<Vec<_>>();
+        let mut out_buffer_len = 0;
+        get_names(buffer_len, &mut out_buffer_len, ptrs.as_mut_ptr())?;
+        if out_buffer_len > buffer_len {
+            buffer_len = out_buffer_len;
+            continue;
+        }
+        return buffers
+            .iter()
+            .map(|buffer| {
+                let len = buffer.iter().position(|c| *c == 0).unwrap_or(buffer.len());
+                String::from_utf8(buffer[..len].to_vec()).map_err(|e| Error::new(e.to_string()))
+            })
+            .collect();
+    }
+}
+
+/// `None` for fields which are not set.
+fn non_empty<T>(values: Vec<T>) -> Option<Vec<T>> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values)
+    }
+}
+
+/// Convert per-row query ids to the sizes of the groups of contiguous rows of one query.
+pub(crate) fn group_sizes<Q: Eq + Hash + Display>(query_ids: &[Q]) -> Result<Vec<i32>> {
+    let mut sizes: Vec<i32> = Vec::new();
+    let mut seen = HashSet::new();
+    for (idx, query_id) in query_ids.iter().enumerate() {
+        if idx > 0 && query_ids[idx - 1] == *query_id {
+            *sizes.last_mut().unwrap() += 1;
+            continue;
+        }
+        if !seen.insert(query_id) {
+            return Err(Error::new(format!(
+                "rows of query {} are not contiguous",
+                query_id
+            )));
+        }
+        sizes.push(1);
+    }
+    Ok(sizes)
+}
+
+/// Check that a sparse matrix in CSR or CSC format is well-formed, `inner_len` is the number
+/// of columns for CSR and the number of rows for CSC.
+pub(crate) fn check_compressed<P: IndexPtr, T>(
+    indptr: &[P],
+    indices: &[i32],
+    values: &[T],
+    inner_len: usize,
+) -> Result<()> {
+    if indptr.is_empty() {
+        return Err(Error::new(
+            "index pointer has to contain at least one element",
+        ));
+    }
+    if indices.len() != values.len() {
+        return Err(Error::new(format!(
+            "got {} indices, but {} values",
+            indices.len(),
+            values.len()
+        )));
+    }
+    // checked in place, as this runs for every row of the fast single row prediction
+    let position = |p: &P| -> i64 { (*p).into() };
+    if position(&indptr[0]) != 0
+        || indptr.windows(2).any(|w| position(&w[0]) > position(&w[1]))
+        || position(&indptr[indptr.len() - 1]) != values.len() as i64
+    {
+        return Err(Error::new(format!(
+            "index pointer has to increase from 0 to the number of values {}",
+            values.len()
+        )));
+    }
+    if let Some(index) = indices
+        .iter()
+        .find(|i| **i < 0 || **i as usize >= inner_len)
+    {
+        return Err(Error::new(format!(
+            "index {} out of bounds for length {}",
+            index, inner_len
+        )));
+    }
+    if indptr.len() - 1 > i32::MAX as usize || inner_len > i32::MAX as usize {
+        return Err(Error::new(format!(
+            "received sparse matrix with {} and {} entries per dimension, but at most {} is supported",
+            indptr.len() - 1,
+            inner_len,
+            i32::MAX
+        )));
+    }
+    Ok(())
 }
 
 impl Drop for Dataset {
@@ -256,9 +1404,10 @@ impl Drop for Dataset {
 #[cfg(test)]
 mod tests {
     use super::*;
-    fn read_train_file() -> Result<Dataset> {
-        Dataset::from_file(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.train")
-    }
+    use crate::test_utils::read_train_file;
+    use crate::{Booster, DatasetBuilder};
+    use serde_json::json;
+    use std::fs;
 
     #[test]
     fn read_file() {
@@ -279,6 +1428,265 @@ mod tests {
         assert!(dataset.is_ok());
     }
 
+    #[test]
+    fn from_file_with_reference() {
+        let train = read_train_file().unwrap();
+        let valid = Dataset::from_file_with_reference(
+            "lightgbm-sys/lightgbm/examples/binary_classification/binary.test",
+            &train,
+        )
+        .unwrap();
+        assert_eq!(valid.get_feature_count(), train.get_feature_count());
+    }
+
+    #[test]
+    fn from_dense() {
+        let label = vec![0.0, 0.0, 0.0, 1.0, 1.0];
+        let row_major: Vec<f64> = vec![
+            1.0, 0.1, 0.2, 0.1, 0.7, 0.4, 0.5, 0.1, 0.9, 0.8, 0.5, 0.1, 0.2, 0.2, 0.8, 0.7, 0.1,
+            0.7, 1.0, 0.9,
+        ];
+        let matrix = DenseMatrix::new(&row_major, 5, 4, Layout::RowMajor).unwrap();
+        let dataset = Dataset::from_dense(matrix, &label).unwrap();
+        assert_eq!(dataset.get_data_len(), Ok(5));
+        assert_eq!(dataset.get_feature_count(), Ok(4));
+        assert_eq!(dataset.get_label(), Ok(label.clone()));
+
+        let column_major: Vec<f32> = (0..20)
+            .map(|i| row_major[(i % 5) * 4 + i / 5] as f32)
+            .collect();
+        let matrix = DenseMatrix::new(&column_major, 5, 4, Layout::ColumnMajor).unwrap();
+        let valid = Dataset::from_dense_with_reference(matrix, &label, &dataset).unwrap();
+        assert_eq!(valid.get_data_len(), Ok(5));
+        assert_eq!(valid.get_feature_count(), Ok(4));
+
+        assert!(Dataset::from_dense(matrix, &label[1..]).is_err());
+    }
+
+    #[test]
+    fn from_csr() {
+        // [[1.0, 0.0, 0.0, 0.5],
+        //  [0.0, 0.0, 2.0, 0.0],
+        //  [0.0, 3.0, 4.0, 0.0],
+        //  [0.0, 0.0, 0.0, 0.0],
+        //  [5.0, 0.0, 0.0, 1.0]]
+        let indptr: Vec<i64> = vec![0, 2, 3, 5, 5, 7];
+        let indices = vec![0, 3, 2, 1, 2, 0, 3];
+        let values: Vec<f32> = vec![1.0, 0.5, 2.0, 3.0, 4.0, 5.0, 1.0];
+        let label = vec![0.0, 0.0, 1.0, 1.0, 1.0];
+        let dataset = Dataset::from_csr(&indptr, &indices, &values, 4, label.clone()).unwrap();
+        assert_eq!(dataset.get_data_len(), Ok(5));
+        assert_eq!(dataset.get_feature_count(), Ok(4));
+        assert_eq!(dataset.get_label(), Ok(label));
+
+        let indptr: Vec<i32> = vec![0, 2, 3, 5, 5, 7];
+        let values: Vec<f64> = vec![1.0, 0.5, 2.0, 3.0, 4.0, 5.0, 1.0];
+        let label = vec![0.0, 0.0, 1.0, 1.0, 1.0];
+        assert!(Dataset::from_csr(&indptr, &indices, &values, 4, label.clone()).is_ok());
+
+        let params = DatasetBuilder::default()
+            .max_bin(15)
+            .min_data_in_bin(1)
+            .build()
+            .unwrap();
+        let train = Dataset::from_csr_with_params(
+            &indptr,
+            &indices,
+            &values,
+            4,
+            label.clone(),
+            &params,
+            None,
+        )
+        .unwrap();
+        assert!(train.check_train_params(&json! {{"max_bin": 63}}).is_err());
+        let valid =
+            Dataset::from_csr_with_reference(&indptr, &indices, &values, 4, label, &train).unwrap();
+        assert_eq!(valid.get_feature_count(), Ok(4));
+    }
+
+    #[test]
+    fn from_csr_invalid() {
+        let indices = vec![0, 2];
+        let values = vec![1.0, 2.0];
+        let label = vec![0.0, 1.0];
+        // index pointer does not end at the number of values
+        assert!(Dataset::from_csr(&[0, 1, 1], &indices, &values, 3, label.clone()).is_err());
+        // column index out of bounds
+        assert!(Dataset::from_csr(&[0, 1, 2], &indices, &values, 2, label.clone()).is_err());
+        // wrong number of labels
+        assert!(Dataset::from_csr(&[0, 1, 2], &indices, &values, 3, vec![0.0]).is_err());
+    }
+
+    #[test]
+    fn from_csc() {
+        // same matrix as in from_csr
+        let col_ptr: Vec<i32> = vec![0, 2, 3, 5, 7];
+        let row_indices = vec![0, 4, 2, 1, 2, 0, 4];
+        let values: Vec<f64> = vec![1.0, 5.0, 3.0, 2.0, 4.0, 0.5, 1.0];
+        let label = vec![0.0, 0.0, 1.0, 1.0, 1.0];
+        let dataset = Dataset::from_csc(&col_ptr, &row_indices, &values, 5, label.clone()).unwrap();
+        assert_eq!(dataset.get_data_len(), Ok(5));
+        assert_eq!(dataset.get_feature_count(), Ok(4));
+        assert_eq!(dataset.get_label(), Ok(label.clone()));
+
+        let params = DatasetBuilder::default()
+            .max_bin(15)
+            .min_data_in_bin(1)
+            .build()
+            .unwrap();
+        let train = Dataset::from_csc_with_params(
+            &col_ptr,
+            &row_indices,
+            &values,
+            5,
+            label.clone(),
+            &params,
+            None,
+        )
+        .unwrap();
+        assert!(train.check_train_params(&json! {{"max_bin": 63}}).is_err());
+        let valid =
+            Dataset::from_csc_with_reference(&col_ptr, &row_indices, &values, 5, label, &train)
+                .unwrap();
+        assert_eq!(valid.get_feature_count(), Ok(4));
+
+        // row index out of bounds
+        let label = vec![0.0, 0.0, 1.0, 1.0];
+        assert!(Dataset::from_csc(&col_ptr, &row_indices, &values, 4, label).is_err());
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn from_array() {
+        use ndarray::{array, Array1};
+
+        let data = array![
+            [1.0_f32, 0.1, 0.2, 0.1],
+            [0.7, 0.4, 0.5, 0.1],
+            [0.9, 0.8, 0.5, 0.1],
+            [0.2, 0.2, 0.8, 0.7],
+            [0.1, 0.7, 1.0, 0.9]
+        ];
+        let label: Array1<f32> = array![0.0, 0.0, 0.0, 1.0, 1.0];
+        let dataset = Dataset::from_array(data.view(), label.view()).unwrap();
+        assert_eq!(dataset.get_data_len(), Ok(5));
+        assert_eq!(dataset.get_feature_count(), Ok(4));
+        assert_eq!(dataset.get_label(), Ok(label.to_vec()));
+
+        let transposed = Dataset::from_array(data.t(), label.slice(ndarray::s![..4])).unwrap();
+        assert_eq!(transposed.get_data_len(), Ok(4));
+        assert_eq!(transposed.get_feature_count(), Ok(5));
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn from_arrow() {
+        use arrow::array::{ArrayRef, Float32Array, Float64Array, Int32Array};
+        use std::sync::Arc;
+
+        let batch = |offset: f64| {
+            RecordBatch::try_from_iter(vec![
+                (
+                    "feature_1",
+                    Arc::new(Float64Array::from(vec![offset, 0.7, 0.2])) as ArrayRef,
+                ),
+                (
+                    "feature_2",
+                    Arc::new(Float32Array::from(vec![0.1, 0.4, offset as f32])) as ArrayRef,
+                ),
+            ])
+            .unwrap()
+        };
+        let batches = vec![batch(1.0), batch(0.5)];
+        let label = Float32Array::from(vec![0.0, 0.0, 1.0, 1.0, 0.0, 1.0]);
+        let mut dataset = Dataset::from_arrow(&batches, &label).unwrap();
+        assert_eq!(dataset.get_data_len(), Ok(6));
+        assert_eq!(dataset.get_feature_count(), Ok(2));
+        assert_eq!(dataset.get_label(), Ok(vec![0.0, 0.0, 1.0, 1.0, 0.0, 1.0]));
+
+        let weights = Float64Array::from(vec![1.0, 2.0, 1.0, 2.0, 1.0, 2.0]);
+        dataset.set_field_from_arrow("weight", &weights).unwrap();
+        assert_eq!(
+            dataset.get_weights(),
+            Ok(Some(vec![1.0, 2.0, 1.0, 2.0, 1.0, 2.0]))
+        );
+        dataset
+            .set_field_from_arrow("group", &Int32Array::from(vec![3, 3]))
+            .unwrap();
+
+        let params = DatasetBuilder::default()
+            .max_bin(15)
+            .min_data_in_bin(1)
+            .build()
+            .unwrap();
+        let train = Dataset::from_arrow_with_params(&batches, &label, &params, None).unwrap();
+        assert!(train.check_train_params(&json! {{"max_bin": 63}}).is_err());
+        let valid =
+            Dataset::from_arrow_with_params(&batches, &label, &params, Some(&train)).unwrap();
+        assert_eq!(valid.get_feature_count(), Ok(2));
+
+        assert!(Dataset::from_arrow(&[], &label).is_err());
+        assert!(dataset
+            .set_field_from_arrow("weight", &Float64Array::from(vec![Some(1.0), None]))
+            .is_err());
+    }
+
+    #[test]
+    fn group_sizes_from_query_ids() {
+        assert_eq!(group_sizes(&[7, 7, 3, 5, 5, 5]), Ok(vec![2, 1, 3]));
+        assert_eq!(group_sizes::<i64>(&[]), Ok(vec![]));
+        assert!(group_sizes(&[1, 2, 1]).is_err());
+    }
+
+    #[cfg(feature = "dataframe")]
+    #[test]
+    fn from_dataframe_with_options() {
+        use polars::df;
+        let df: DataFrame = df![
+            "amount" => [Some(10.0), Some(7.5), None, Some(2.0), Some(1.0), Some(3.0)],
+            "country" => ["DE", "FR", "DE", "US", "FR", "DE"],
+            "weight" => [1.0, 1.0, 2.0, 1.0, 2.0, 1.0],
+            "query" => [1, 1, 1, 2, 2, 2],
+            "label" => [0.0, 0.0, 1.0, 1.0, 0.0, 1.0]
+        ]
+        .unwrap();
+        let options = DataFrameOptions::new("label")
+            .weight_column("weight")
+            .group_column("query");
+        let mut encoder = CategoryEncoder::new();
+        let dataset = Dataset::from_dataframe_with_options(&df, &options, &mut encoder).unwrap();
+        assert_eq!(encoder.categories("country").unwrap(), &["DE", "FR", "US"]);
+        assert_eq!(dataset.get_data_len(), Ok(6));
+        assert_eq!(dataset.get_feature_count(), Ok(2));
+        assert_eq!(
+            dataset.get_weights(),
+            Ok(Some(vec![1.0, 1.0, 2.0, 1.0, 2.0, 1.0]))
+        );
+
+        let options = DataFrameOptions::new("label")
+            .weight_column("weight")
+            .group_column("query")
+            .dataset_params(
+                DatasetBuilder::default()
+                    .min_data_in_bin(1)
+                    .build()
+                    .unwrap(),
+            );
+        let dataset = Dataset::from_dataframe_with_options(&df, &options, &mut encoder).unwrap();
+        assert!(dataset
+            .check_train_params(&json! {{"categorical_feature": "1", "min_data_in_bin": 1}})
+            .is_ok());
+        assert!(dataset
+            .check_train_params(&json! {{"categorical_feature": "0"}})
+            .is_err());
+
+        let options = DataFrameOptions::new("label").null_values(crate::NullValues::Error);
+        assert!(Dataset::from_dataframe_with_options(&df, &options, &mut encoder).is_err());
+        let options = DataFrameOptions::new("missing");
+        assert!(Dataset::from_dataframe_with_options(&df, &options, &mut encoder).is_err());
+    }
+
     #[cfg(feature = "dataframe")]
     #[test]
     fn from_dataframe() {
@@ -294,6 +1702,14 @@ mod tests {
 
         let df_dataset = Dataset::from_dataframe(df, String::from("label"));
         assert!(df_dataset.is_ok());
+
+        // the categories of string columns could not be encoded for prediction
+        let df: DataFrame = df![
+            "country" => ["DE", "FR", "DE"],
+            "label" => [0.0, 1.0, 1.0]
+        ]
+        .unwrap();
+        assert!(Dataset::from_dataframe(df, String::from("label")).is_err());
     }
 
     #[test]
@@ -311,6 +1727,167 @@ mod tests {
         assert_eq!(dataset.get_feature_count(), Ok(4));
     }
 
+    #[test]
+    fn get_label_and_weights() {
+        let data = vec![
+            vec![1.0, 0.1, 0.2, 0.1],
+            vec![0.7, 0.4, 0.5, 0.1],
+            vec![0.9, 0.8, 0.5, 0.1],
+        ];
+        let label = vec![0.0, 1.0, 1.0];
+        let mut dataset = Dataset::from_mat(data, label.clone()).unwrap();
+        assert_eq!(dataset.get_label(), Ok(label));
+        assert_eq!(dataset.get_weights(), Ok(None));
+        dataset.set_weights(vec![0.5, 1.0, 2.0]).unwrap();
+        assert_eq!(dataset.get_weights(), Ok(Some(vec![0.5, 1.0, 2.0])));
+    }
+
+    #[test]
+    fn get_and_set_fields() {
+        let data = vec![
+            vec![1.0, 0.1, 0.2, 0.1],
+            vec![0.7, 0.4, 0.5, 0.1],
+            vec![0.9, 0.8, 0.5, 0.1],
+            vec![0.2, 0.2, 0.8, 0.7],
+        ];
+        let mut dataset = Dataset::from_mat(data, vec![0.0, 1.0, 2.0, 1.0]).unwrap();
+        assert_eq!(dataset.get_init_score(), Ok(None));
+        assert_eq!(dataset.get_groups(), Ok(None));
+        assert_eq!(dataset.get_positions(), Ok(None));
+
+        dataset.set_label(&[1.0, 0.0, 1.0, 2.0]).unwrap();
+        assert_eq!(dataset.get_label(), Ok(vec![1.0, 0.0, 1.0, 2.0]));
+        assert!(dataset.set_label(&[1.0, 0.0]).is_err());
+
+        // multiclass layout with 3 classes
+        let init_score = (0..12).map(f64::from).collect::<Vec<_>>();
+        dataset.set_init_score(&init_score).unwrap();
+        assert_eq!(dataset.get_init_score(), Ok(Some(init_score)));
+        assert!(dataset.set_init_score(&[0.5; 6]).is_err());
+        assert!(dataset.set_init_score(&[]).is_err());
+
+        dataset.set_groups(&[1, 3]).unwrap();
+        assert_eq!(dataset.get_groups(), Ok(Some(vec![1, 3])));
+        dataset.set_positions(&[0, 0, 1, 2]).unwrap();
+        assert_eq!(dataset.get_positions(), Ok(Some(vec![0, 0, 1, 2])));
+    }
+
+    #[test]
+    fn feature_names() {
+        let data = vec![vec![1.0, 0.1, 0.2], vec![0.7, 0.4, 0.5]];
+        let mut dataset = Dataset::from_mat(data, vec![0.0, 1.0]).unwrap();
+        assert_eq!(
+            dataset.feature_names().unwrap(),
+            vec!["Column_0", "Column_1", "Column_2"]
+        );
+        let long_name = "a_feature_name_which_is_longer_than_the_initial_buffer_of_64_bytes";
+        dataset
+            .set_feature_names(&["amount", "age", long_name])
+            .unwrap();
+        assert_eq!(
+            dataset.feature_names().unwrap(),
+            vec!["amount", "age", long_name]
+        );
+        assert!(dataset.set_feature_names(&["amount", "age"]).is_err());
+    }
+
+    #[test]
+    fn from_dense_with_categorical() {
+        let data = vec![1.0, 0.0, 0.7, 2.0, 0.2, 1.0];
+        let matrix = DenseMatrix::new(&data, 3, 2, Layout::RowMajor).unwrap();
+        let label = [0.0, 0.0, 1.0];
+        let params = DatasetBuilder::default()
+            .min_data_in_bin(1)
+            .build()
+            .unwrap();
+        let dataset = Dataset::from_dense_with_categorical(
+            matrix,
+            &label,
+            Some(&["amount", "city"]),
+            &["city".into()],
+            &params,
+        )
+        .unwrap();
+        assert_eq!(dataset.feature_names().unwrap(), vec!["amount", "city"]);
+        // training parameters are checked against the categorical declaration too
+        assert!(dataset
+            .check_train_params(&json! {{"categorical_feature": "1", "min_data_in_bin": 1}})
+            .is_ok());
+        assert!(dataset
+            .check_train_params(&json! {{"categorical_feature": "0"}})
+            .is_err());
+
+        let default = DatasetParams::default();
+        assert!(
+            Dataset::from_dense_with_categorical(matrix, &label, None, &[1.into()], &default)
+                .is_ok()
+        );
+        assert!(Dataset::from_dense_with_categorical(
+            matrix,
+            &label,
+            None,
+            &["city".into()],
+            &default
+        )
+        .is_err());
+        let params = DatasetBuilder::default()
+            .extra("categorical_feature", "0")
+            .build()
+            .unwrap();
+        assert!(
+            Dataset::from_dense_with_categorical(matrix, &label, None, &[1.into()], &params)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn from_file_with_params() {
+        let path = "./test/test_dataset_params.csv";
+        fs::write(
+            path,
+            "id,amount,age,target\n1,1.0,0.1,0\n2,0.7,0.4,0\n3,0.9,0.8,1\n4,0.2,0.2,1\n",
+        )
+        .unwrap();
+        let params = DatasetBuilder::default()
+            .header(true)
+            .label_column("name:target")
+            .ignore_column("name:id")
+            .max_bin(15)
+            .min_data_in_bin(1)
+            .build()
+            .unwrap();
+        let dataset = Dataset::from_file_with_params(path, &params, None);
+        let _ = fs::remove_file(path);
+        let dataset = dataset.unwrap();
+        assert_eq!(dataset.get_data_len(), Ok(4));
+        assert_eq!(dataset.get_label(), Ok(vec![0.0, 0.0, 1.0, 1.0]));
+
+        assert!(dataset
+            .check_train_params(&json! {{"max_bin": 15, "objective": "binary"}})
+            .is_ok());
+        assert!(dataset
+            .check_train_params(&json! {{"max_bin": 63}})
+            .is_err());
+        assert!(Booster::train(dataset, &json! {{"max_bin": 63}}).is_err());
+    }
+
+    #[test]
+    fn from_mat_with_params() {
+        let data = vec![vec![1.0, 0.1], vec![0.7, 0.4], vec![0.9, 0.8]];
+        let params = DatasetBuilder::default()
+            .min_data_in_bin(1)
+            .build()
+            .unwrap();
+        let train = Dataset::from_mat_with_params(data.clone(), vec![0.0, 1.0, 1.0], &params, None)
+            .unwrap();
+        let valid = Dataset::from_mat_with_params(data, vec![0.0, 1.0, 1.0], &params, Some(&train))
+            .unwrap();
+        assert_eq!(valid.get_feature_count(), train.get_feature_count());
+        // datasets without parameters keep accepting any training parameters
+        let plain = Dataset::from_mat(vec![vec![1.0, 0.1]], vec![0.0]).unwrap();
+        assert!(!plain.has_params());
+    }
+
     #[test]
     fn set_weights() {
         let data = vec![
@@ -326,6 +1903,35 @@ mod tests {
         dataset.set_weights(weights).unwrap();
     }
 
+    #[test]
+    fn set_groups() {
+        let data = vec![
+            vec![1.0, 0.1, 0.2, 0.1],
+            vec![0.7, 0.4, 0.5, 0.1],
+            vec![0.9, 0.8, 0.5, 0.1],
+            vec![0.2, 0.2, 0.8, 0.7],
+            vec![0.1, 0.7, 1.0, 0.9],
+        ];
+        let label = vec![0.0, 2.0, 1.0, 1.0, 0.0];
+        let mut dataset = Dataset::from_mat(data, label).unwrap();
+        dataset.set_groups(&[3, 2]).unwrap();
+        assert!(dataset.set_groups(&[3, 1]).is_err());
+        assert!(dataset.set_groups(&[3, 0, 2]).is_err());
+
+        dataset
+            .set_groups_from_query_ids(&[10, 10, 10, 20, 20])
+            .unwrap();
+        assert!(dataset
+            .set_groups_from_query_ids(&[10, 10, 10, 20])
+            .is_err());
+        assert!(dataset
+            .set_groups_from_query_ids(&[10, 20, 10, 20, 20])
+            .is_err());
+
+        dataset.set_positions(&[0, 1, 2, 0, 1]).unwrap();
+        assert!(dataset.set_positions(&[0, 1, 2]).is_err());
+    }
+
     #[test]
     fn set_weights_wrong_len() {
         let data = vec![
@@ -0,0 +1,81 @@
+//! Element types accepted by LightGBM's C API.
+
+use lightgbm_sys;
+
+use crate::Result;
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+    impl Sealed for i32 {}
+    impl Sealed for i64 {}
+}
+
+/// Floating point type of feature values, `f32` or `f64`.
+pub trait FeatureValue: sealed::Sealed + Copy {
+    /// LightGBM's `C_API_DTYPE_*` constant for this type.
+    const DTYPE: i32;
+    /// Missing value.
+    const NAN: Self;
+}
+
+impl FeatureValue for f32 {
+    const DTYPE: i32 = lightgbm_sys::C_API_DTYPE_FLOAT32 as i32;
+    const NAN: Self = f32::NAN;
+}
+
+impl FeatureValue for f64 {
+    const DTYPE: i32 = lightgbm_sys::C_API_DTYPE_FLOAT64 as i32;
+    const NAN: Self = f64::NAN;
+}
+
+/// Floating point type predictions can be written to, `f32` or `f64`.
+///
+/// LightGBM always predicts `f64` values, which are converted when writing to `f32`.
+pub trait PredictionValue: sealed::Sealed + Copy {
+    /// Let `predict` write into an `f64` buffer of the length of `output` and store the
+    /// result in `output`.
+    #[doc(hidden)]
+    fn write_from_f64<F>(output: &mut [Self], predict: F) -> Result<()>
+    where
+        F: FnOnce(&mut [f64]) -> Result<()>;
+}
+
+impl PredictionValue for f32 {
+    fn write_from_f64<F>(output: &mut [Self], predict: F) -> Result<()>
+    where
+        F: FnOnce(&mut [f64]) -> Result<()>,
+    {
+        let mut buffer = vec![0.0; output.len()];
+        predict(&mut buffer)?;
+        for (out, value) in output.iter_mut().zip(buffer) {
+            *out = value as f32;
+        }
+        Ok(())
+    }
+}
+
+impl PredictionValue for f64 {
+    fn write_from_f64<F>(output: &mut [Self], predict: F) -> Result<()>
+    where
+        F: FnOnce(&mut [f64]) -> Result<()>,
+    {
+        predict(output)
+    }
+}
+
+/// Integer type of the index pointers of sparse matrices, `i32` or `i64`.
+pub trait IndexPtr: sealed::Sealed + Copy + Into<i64> {
+    /// LightGBM's `C_API_DTYPE_*` constant for this type.
+    const DTYPE: i32;
+}
+
+impl IndexPtr for i32 {
+    const DTYPE: i32 = lightgbm_sys::C_API_DTYPE_INT32 as i32;
+}
+
+impl IndexPtr for i64 {
+    const DTYPE: i32 = lightgbm_sys::C_API_DTYPE_INT64 as i32;
+}
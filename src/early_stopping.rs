@@ -0,0 +1,236 @@
+//! Early stopping on validation metrics.
+
+use serde_json::Value;
+
+use crate::training::EvalResult;
+use crate::{Error, Result};
+
+/// Whether larger values of the LightGBM metric `name` are better.
+pub(crate) fn is_higher_better(name: &str) -> bool {
+    ["auc", "ndcg@", "map@", "average_precision"]
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
+
+/// Tracks the best score of every watched (validation dataset, metric) pair and decides when
+/// training should stop.
+pub(crate) struct EarlyStopping {
+    rounds: usize,
+    min_delta: f64,
+    first_metric_only: bool,
+    metric: Option<String>,
+    // best (score, iteration) per watched pair, in the order of the evaluation results
+    best: Vec<(f64, usize)>,
+    stopped_at: Option<usize>,
+}
+
+impl EarlyStopping {
+    /// Read the early stopping settings from the training parameters.
+    ///
+    /// Returns `None` unless `early_stopping_round` is positive. Only `metric` is watched if
+    /// it is given, else only the first metric in the order of the `metric` parameter if
+    /// `first_metric_only` is set, otherwise all of them are.
+    pub(crate) fn from_params(parameter: &Value, metric: Option<String>) -> Result<Option<Self>> {
+        let rounds = read_param(
+            parameter,
+            "early_stopping_round",
+            0,
+            Value::as_i64,
+            "an integer",
+        )?;
+        if rounds <= 0 {
+            return Ok(None);
+        }
+        let min_delta = read_param(
+            parameter,
+            "early_stopping_min_delta",
+            0.0,
+            Value::as_f64,
+            "a number",
+        )?;
+        if min_delta < 0.0 {
+            return Err(Error::new(format!(
+                "early_stopping_min_delta must be non-negative, got {}",
+                min_delta
+            )));
+        }
+        Ok(Some(Self {
+            rounds: rounds as usize,
+            min_delta,
+            first_metric_only: read_param(
+                parameter,
+                "first_metric_only",
+                false,
+                Value::as_bool,
+                "a boolean",
+            )?,
+            metric,
+            best: Vec::new(),
+            stopped_at: None,
+        }))
+    }
+
+    /// Record the evaluation results after `iteration` (0-based).
+    ///
+    /// Results on the training dataset are ignored. Returns `true` if training should stop.
+    pub(crate) fn update(&mut self, iteration: usize, results: &[EvalResult]) -> Result<bool> {
+        // LightGBM evaluates the built-in metrics in the order of the `metric` parameter
+        let first_metric = results
+            .iter()
+            .find(|result| !result.is_training())
+            .map(|result| result.metric_name.as_str());
+        let watched_metric = match &self.metric {
+            Some(metric) => Some(metric.as_str()),
+            None if self.first_metric_only => first_metric,
+            None => None,
+        };
+        let watched = results.iter().filter(|result| {
+            !result.is_training()
+                && (watched_metric.is_none() || watched_metric == Some(result.metric_name.as_str()))
+        });
+
+        let mut num_watched = 0;
+        for (idx, result) in watched.enumerate() {
+            num_watched += 1;
+            if idx == self.best.len() {
+                self.best.push((result.value, iteration));
+                continue;
+            }
+            let (best_score, best_iteration) = self.best[idx];
+            let improved = if result.higher_is_better {
+                result.value > best_score + self.min_delta
+            } else {
+                result.value < best_score - self.min_delta
+            };
+            if improved {
+                self.best[idx] = (result.value, iteration);
+            } else if iteration - best_iteration >= self.rounds {
+                self.stopped_at = Some(best_iteration);
+                return Ok(true);
+            }
+        }
+        if num_watched == 0 {
+            return Err(match &self.metric {
+                Some(metric) => Error::new(format!(
+                    "early stopping metric {} is not evaluated on a validation dataset",
+                    metric
+                )),
+                None => {
+                    Error::new("early stopping requires at least one validation dataset and metric")
+                }
+            });
+        }
+        Ok(false)
+    }
+
+    /// The number of iterations giving the best score, i.e. the 1-based best iteration.
+    pub(crate) fn best_iteration(&self) -> Option<i32> {
+        self.stopped_at
+            .or_else(|| self.best.first().map(|(_, iteration)| *iteration))
+            .map(|iteration| iteration as i32 + 1)
+    }
+}
+
+/// Read the parameter `name` with `read`, `default` if it is not set.
+fn read_param<T>(
+    parameter: &Value,
+    name: &str,
+    default: T,
+    read: fn(&Value) -> Option<T>,
+    expected: &str,
+) -> Result<T> {
+    match &parameter[name] {
+        Value::Null => Ok(default),
+        value => read(value)
+            .ok_or_else(|| Error::new(format!("{} must be {}, got {}", name, expected, value))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn results(auc: f64, logloss: f64) -> Vec<EvalResult> {
+        vec![
+            EvalResult::new("training", "auc", 1.0, true),
+            EvalResult::new("valid", "auc", auc, true),
+            EvalResult::new("valid", "binary_logloss", logloss, false),
+        ]
+    }
+
+    #[test]
+    fn metric_direction() {
+        assert!(is_higher_better("auc"));
+        assert!(is_higher_better("ndcg@5"));
+        assert!(!is_higher_better("l2"));
+        assert!(!is_higher_better("binary_logloss"));
+    }
+
+    #[test]
+    fn disabled_without_rounds() {
+        let params = json! {{"objective": "binary"}};
+        assert!(EarlyStopping::from_params(&params, None).unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_wrong_types() {
+        for params in [
+            json! {{"early_stopping_round": "5"}},
+            json! {{"early_stopping_round": 5.5}},
+            json! {{"early_stopping_round": 5, "early_stopping_min_delta": "0.1"}},
+            json! {{"early_stopping_round": 5, "first_metric_only": 1}},
+        ]
+        .iter()
+        {
+            assert!(EarlyStopping::from_params(params, None).is_err());
+        }
+    }
+
+    #[test]
+    fn requires_valid_set() {
+        let params = json! {{"early_stopping_round": 2}};
+        let mut es = EarlyStopping::from_params(&params, None).unwrap().unwrap();
+        let training_only = vec![EvalResult::new("training", "auc", 1.0, true)];
+        assert!(es.update(0, &training_only).is_err());
+    }
+
+    #[test]
+    fn stops_after_rounds_without_improvement() {
+        let params = json! {{"early_stopping_round": 2, "first_metric_only": true}};
+        let mut es = EarlyStopping::from_params(&params, None).unwrap().unwrap();
+        assert_eq!(es.update(0, &results(0.7, 0.5)), Ok(false));
+        assert_eq!(es.update(1, &results(0.8, 0.6)), Ok(false));
+        assert_eq!(es.update(2, &results(0.75, 0.7)), Ok(false));
+        assert_eq!(es.update(3, &results(0.79, 0.8)), Ok(true));
+        assert_eq!(es.best_iteration(), Some(2));
+    }
+
+    #[test]
+    fn watches_chosen_metric() {
+        let params = json! {{"early_stopping_round": 2}};
+        let metric = Some(String::from("binary_logloss"));
+        let mut es = EarlyStopping::from_params(&params, metric)
+            .unwrap()
+            .unwrap();
+        assert_eq!(es.update(0, &results(0.7, 0.5)), Ok(false));
+        assert_eq!(es.update(1, &results(0.8, 0.6)), Ok(false));
+        assert_eq!(es.update(2, &results(0.9, 0.7)), Ok(true));
+        assert_eq!(es.best_iteration(), Some(1));
+
+        let metric = Some(String::from("l2"));
+        let mut es = EarlyStopping::from_params(&params, metric)
+            .unwrap()
+            .unwrap();
+        assert!(es.update(0, &results(0.7, 0.5)).is_err());
+    }
+
+    #[test]
+    fn min_delta() {
+        let params = json! {{"early_stopping_round": 1, "early_stopping_min_delta": 0.1}};
+        let mut es = EarlyStopping::from_params(&params, None).unwrap().unwrap();
+        assert_eq!(es.update(0, &results(0.7, 0.5)), Ok(false));
+        assert_eq!(es.update(1, &results(0.75, 0.45)), Ok(true));
+        assert_eq!(es.best_iteration(), Some(1));
+    }
+}
@@ -52,6 +52,12 @@ impl Display for Error {
     }
 }
 
+impl From<derive_builder::UninitializedFieldError> for Error {
+    fn from(e: derive_builder::UninitializedFieldError) -> Self {
+        Self::new(e.to_string())
+    }
+}
+
 #[cfg(feature = "dataframe")]
 impl From<PolarsError> for Error {
     fn from(pe: PolarsError) -> Self {
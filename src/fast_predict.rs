@@ -0,0 +1,241 @@
+//! Low-latency prediction of single rows.
+
+use libc::c_char;
+use std;
+use std::ffi::CString;
+use std::marker::PhantomData;
+
+use lightgbm_sys;
+
+use crate::dataset::check_compressed;
+use crate::{Booster, Error, FeatureValue, IterationRange, PredictType, Result};
+
+/// Prepared configuration of LightGBM's single row fast prediction, freed on drop.
+struct FastConfig(lightgbm_sys::FastConfigHandle);
+
+impl Drop for FastConfig {
+    fn drop(&mut self) {
+        // an error can't be reported from drop, and panicking here could abort the process
+        let _ = lgbm_call!(lightgbm_sys::LGBM_FastConfigFree(self.0));
+    }
+}
+
+/// Predicts single dense rows without the per call overhead of [`Booster::predict`].
+///
+/// The prediction type, iteration range and feature type are fixed when the predictor is
+/// created, and every call writes into a buffer provided by the caller, so nothing is
+/// allocated per row. Use one predictor per thread, predicting takes `&mut self` as LightGBM's
+/// fast configuration must not be used concurrently.
+///
+/// Example
+/// ```no_run
+/// use lightgbm::{Booster, FastPredictor, IterationRange, PredictType};
+///
+/// let booster = Booster::from_file("path/to/model.txt").unwrap();
+/// let mut predictor: FastPredictor<f64> =
+///     FastPredictor::new(&booster, PredictType::Normal, IterationRange::default()).unwrap();
+/// let mut output = vec![0.0; predictor.output_len()];
+/// predictor.predict(&[1.0, 0.1, 0.2], &mut output).unwrap();
+/// ```
+pub struct FastPredictor<'a, T: FeatureValue> {
+    config: FastConfig,
+    num_feature: usize,
+    output_len: usize,
+    _booster: PhantomData<&'a Booster>,
+    _dtype: PhantomData<T>,
+}
+
+impl<'a, T: FeatureValue> FastPredictor<'a, T> {
+    pub fn new(
+        booster: &'a Booster,
+        predict_type: PredictType,
+        range: IterationRange,
+    ) -> Result<Self> {
+        let params = CString::new("").unwrap();
+        let num_feature = booster.num_feature()?;
+        let (start_iteration, num_iteration) = range.c_args(booster.best_iteration());
+        let mut handle = std::ptr::null_mut();
+        lgbm_call!(lightgbm_sys::LGBM_BoosterPredictForMatSingleRowFastInit(
+            booster.handle,
+            predict_type.as_c_int(),
+            start_iteration,
+            num_iteration,
+            T::DTYPE,
+            num_feature,
+            params.as_ptr() as *const c_char,
+            &mut handle
+        ))?;
+        let config = FastConfig(handle);
+
+        Ok(Self {
+            config,
+            num_feature: num_feature as usize,
+            output_len: booster.predict_row_len(predict_type, start_iteration, num_iteration)?,
+            _booster: PhantomData,
+            _dtype: PhantomData,
+        })
+    }
+
+    /// Number of values predicted per row, the minimal length of the output buffer.
+    pub fn output_len(&self) -> usize {
+        self.output_len
+    }
+
+    /// Predict `row`, which has one value per feature of the model, into `output`.
+    pub fn predict(&mut self, row: &[T], output: &mut [f64]) -> Result<()> {
+        if row.len() != self.num_feature {
+            return Err(Error::new(format!(
+                "got {} features, but the model has {}",
+                row.len(),
+                self.num_feature
+            )));
+        }
+        check_output_len(output, self.output_len)?;
+        let mut out_len = 0;
+        lgbm_call!(lightgbm_sys::LGBM_BoosterPredictForMatSingleRowFast(
+            self.config.0,
+            row.as_ptr() as *const _,
+            &mut out_len,
+            output.as_mut_ptr()
+        ))?;
+        Ok(())
+    }
+}
+
+/// Predicts single sparse rows without the per call overhead of
+/// [`Booster::predict_csr_single_row`], see [`FastPredictor`].
+///
+/// Example
+/// ```no_run
+/// use lightgbm::{Booster, FastSparsePredictor, IterationRange, PredictType};
+///
+/// let booster = Booster::from_file("path/to/model.txt").unwrap();
+/// let mut predictor: FastSparsePredictor<f32> =
+///     FastSparsePredictor::new(&booster, PredictType::Normal, IterationRange::default())
+///         .unwrap();
+/// let mut output = vec![0.0; predictor.output_len()];
+/// // [0.0, 1.5, 0.0, 0.3]
+/// predictor.predict(&[1, 3], &[1.5, 0.3], &mut output).unwrap();
+/// ```
+pub struct FastSparsePredictor<'a, T: FeatureValue> {
+    config: FastConfig,
+    num_feature: usize,
+    output_len: usize,
+    _booster: PhantomData<&'a Booster>,
+    _dtype: PhantomData<T>,
+}
+
+impl<'a, T: FeatureValue> FastSparsePredictor<'a, T> {
+    pub fn new(
+        booster: &'a Booster,
+        predict_type: PredictType,
+        range: IterationRange,
+    ) -> Result<Self> {
+        let params = CString::new("").unwrap();
+        let num_feature = booster.num_feature()?;
+        let (start_iteration, num_iteration) = range.c_args(booster.best_iteration());
+        let mut handle = std::ptr::null_mut();
+        lgbm_call!(lightgbm_sys::LGBM_BoosterPredictForCSRSingleRowFastInit(
+            booster.handle,
+            predict_type.as_c_int(),
+            start_iteration,
+            num_iteration,
+            T::DTYPE,
+            num_feature as i64,
+            params.as_ptr() as *const c_char,
+            &mut handle
+        ))?;
+        let config = FastConfig(handle);
+
+        Ok(Self {
+            config,
+            num_feature: num_feature as usize,
+            output_len: booster.predict_row_len(predict_type, start_iteration, num_iteration)?,
+            _booster: PhantomData,
+            _dtype: PhantomData,
+        })
+    }
+
+    /// Number of values predicted per row, the minimal length of the output buffer.
+    pub fn output_len(&self) -> usize {
+        self.output_len
+    }
+
+    /// Predict the row given by the feature `indices` of its non-zero `values` into `output`.
+    pub fn predict(&mut self, indices: &[i32], values: &[T], output: &mut [f64]) -> Result<()> {
+        let indptr = [0, values.len() as i64];
+        check_compressed(&indptr, indices, values, self.num_feature)?;
+        check_output_len(output, self.output_len)?;
+        let mut out_len = 0;
+        lgbm_call!(lightgbm_sys::LGBM_BoosterPredictForCSRSingleRowFast(
+            self.config.0,
+            indptr.as_ptr() as *const _,
+            lightgbm_sys::C_API_DTYPE_INT64 as i32,
+            indices.as_ptr(),
+            values.as_ptr() as *const _,
+            indptr.len() as i64,
+            values.len() as i64,
+            &mut out_len,
+            output.as_mut_ptr()
+        ))?;
+        Ok(())
+    }
+}
+
+fn check_output_len(output: &[f64], output_len: usize) -> Result<()> {
+    if output.len() < output_len {
+        return Err(Error::new(format!(
+            "output buffer has length {}, but {} values are predicted per row",
+            output.len(),
+            output_len
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::train_booster_with_iterations;
+
+    #[test]
+    fn fast_predict() {
+        let bst = train_booster_with_iterations(10);
+        let feature = vec![vec![0.5; 28], vec![0.0; 28], vec![0.75; 28]];
+        let expected = bst.predict(feature.clone()).unwrap();
+
+        let mut predictor: FastPredictor<f64> =
+            FastPredictor::new(&bst, PredictType::Normal, IterationRange::default()).unwrap();
+        assert_eq!(predictor.output_len(), 1);
+        let mut output = [0.0];
+        for (row, expected) in feature.iter().zip(&expected[0]) {
+            predictor.predict(row, &mut output).unwrap();
+            assert_eq!(output[0], *expected);
+        }
+        assert!(predictor.predict(&[0.5; 27], &mut output).is_err());
+        assert!(predictor.predict(&feature[0], &mut []).is_err());
+
+        let mut predictor: FastSparsePredictor<f32> =
+            FastSparsePredictor::new(&bst, PredictType::Normal, IterationRange::default()).unwrap();
+        let indices = (0..28).collect::<Vec<_>>();
+        predictor
+            .predict(&indices, &[0.75; 28], &mut output)
+            .unwrap();
+        assert_eq!(output[0], expected[0][2]);
+    }
+
+    #[test]
+    fn fast_predict_contrib() {
+        let bst = train_booster_with_iterations(10);
+        let mut predictor: FastPredictor<f32> =
+            FastPredictor::new(&bst, PredictType::Contrib, IterationRange::default()).unwrap();
+        assert_eq!(predictor.output_len(), 29);
+        let mut output = vec![0.0; predictor.output_len()];
+        predictor.predict(&[0.5; 28], &mut output).unwrap();
+
+        let expected = bst
+            .predict_with_type(vec![vec![0.5_f32; 28]], PredictType::Contrib)
+            .unwrap();
+        assert_eq!(output, expected.row(0));
+    }
+}
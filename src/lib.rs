@@ -1,7 +1,13 @@
+#[macro_use]
+extern crate derive_builder;
 extern crate libc;
 extern crate lightgbm_sys;
 extern crate serde_json;
 
+#[cfg(feature = "arrow")]
+extern crate arrow;
+#[cfg(feature = "ndarray")]
+extern crate ndarray;
 #[cfg(feature = "dataframe")]
 extern crate polars;
 
@@ -15,8 +21,47 @@ macro_rules! lgbm_call {
 mod error;
 pub use error::{Error, Result};
 
+mod dtype;
+pub use dtype::{FeatureValue, IndexPtr, PredictionValue};
+
+mod matrix;
+pub use matrix::{DenseMatrix, Layout};
+
+#[cfg(feature = "arrow")]
+mod arrow_data;
+
+#[cfg(feature = "dataframe")]
+mod dataframe;
+#[cfg(feature = "dataframe")]
+pub use dataframe::{DataFrameOptions, NullValues};
+
+mod categorical;
+pub use categorical::{CategoricalFeature, CategoryEncoder, Column};
+
 mod dataset;
 pub use dataset::Dataset;
 
+mod params;
+pub use params::{
+    BoostingType, DataSampleStrategy, DatasetBuilder, DatasetParams, Metric, Objective,
+    TrainParams, TrainParamsBuilder,
+};
+
+mod early_stopping;
+
+mod training;
+pub use training::{Callback, CallbackAction, CustomMetric, EvalResult, TrainOptions};
+
+mod predict;
+pub use predict::{
+    rank_groups, IterationRange, PredictType, Predictions, SparseFormat, SparseMatrix,
+};
+
 mod booster;
-pub use booster::Booster;
+pub use booster::{Booster, EvalHistory};
+
+mod fast_predict;
+pub use fast_predict::{FastPredictor, FastSparsePredictor};
+
+#[cfg(test)]
+mod test_utils;
@@ -0,0 +1,165 @@
+//! Borrowed dense matrices.
+
+#[cfg(feature = "ndarray")]
+use ndarray::ArrayView2;
+
+use crate::{Error, FeatureValue, Result};
+
+/// Memory layout of a [`DenseMatrix`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Layout {
+    /// Rows are stored one after another (C order).
+    RowMajor,
+    /// Columns are stored one after another (Fortran order).
+    ColumnMajor,
+}
+
+/// Contiguous dense matrix of `f32` or `f64` values, borrowed from the caller and passed to
+/// LightGBM without copying.
+///
+/// Example
+/// ```
+/// use lightgbm::{DenseMatrix, Layout};
+///
+/// // [[1.0, 0.1, 0.2],
+/// //  [0.7, 0.4, 0.5]]
+/// let data = [1.0, 0.1, 0.2, 0.7, 0.4, 0.5];
+/// let matrix = DenseMatrix::new(&data, 2, 3, Layout::RowMajor).unwrap();
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct DenseMatrix<'a, T: FeatureValue> {
+    data: &'a [T],
+    num_rows: usize,
+    num_cols: usize,
+    layout: Layout,
+}
+
+impl<'a, T: FeatureValue> DenseMatrix<'a, T> {
+    /// Wrap `data`, which has to hold exactly `num_rows * num_cols` values.
+    pub fn new(data: &'a [T], num_rows: usize, num_cols: usize, layout: Layout) -> Result<Self> {
+        if num_rows > i32::MAX as usize || num_cols > i32::MAX as usize {
+            return Err(Error::new(format!(
+                "received matrix of size {}x{}, but at most {}x{} is supported",
+                num_rows,
+                num_cols,
+                i32::MAX,
+                i32::MAX
+            )));
+        }
+        if num_rows.checked_mul(num_cols) != Some(data.len()) {
+            return Err(Error::new(format!(
+                "got {} values for a matrix of size {}x{}",
+                data.len(),
+                num_rows,
+                num_cols
+            )));
+        }
+        Ok(Self {
+            data,
+            num_rows,
+            num_cols,
+            layout,
+        })
+    }
+
+    pub fn data(&self) -> &'a [T] {
+        self.data
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// The `is_row_major` argument of the C API.
+    pub(crate) fn is_row_major(&self) -> i32 {
+        match self.layout {
+            Layout::RowMajor => 1,
+            Layout::ColumnMajor => 0,
+        }
+    }
+}
+
+/// Call `f` with a [`DenseMatrix`] of `data`, which borrows its memory if it is standard or
+/// Fortran contiguous and is a row-major copy otherwise.
+#[cfg(feature = "ndarray")]
+pub(crate) fn with_array_view<T, R, F>(data: ArrayView2<T>, f: F) -> Result<R>
+where
+    T: FeatureValue,
+    F: FnOnce(DenseMatrix<T>) -> Result<R>,
+{
+    let (num_rows, num_cols) = data.dim();
+    if let Some(slice) = data.to_slice() {
+        return f(DenseMatrix::new(
+            slice,
+            num_rows,
+            num_cols,
+            Layout::RowMajor,
+        )?);
+    }
+    if let Some(slice) = data.t().to_slice() {
+        return f(DenseMatrix::new(
+            slice,
+            num_rows,
+            num_cols,
+            Layout::ColumnMajor,
+        )?);
+    }
+    let data = data.as_standard_layout();
+    f(DenseMatrix::new(
+        data.as_slice().unwrap(),
+        num_rows,
+        num_cols,
+        Layout::RowMajor,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new() {
+        let data = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let matrix = DenseMatrix::new(&data, 3, 2, Layout::ColumnMajor).unwrap();
+        assert_eq!(matrix.num_rows(), 3);
+        assert_eq!(matrix.num_cols(), 2);
+        assert_eq!(matrix.is_row_major(), 0);
+        assert!(DenseMatrix::new(&data, 2, 2, Layout::RowMajor).is_err());
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn array_view_layout() {
+        use ndarray::{s, Array2, ShapeBuilder};
+
+        let layout = |data: ArrayView2<f64>| {
+            with_array_view(data, |matrix| {
+                Ok((matrix.layout(), matrix.num_rows(), matrix.data().to_vec()))
+            })
+            .unwrap()
+        };
+        let standard = Array2::from_shape_vec((2, 3), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+        assert_eq!(
+            layout(standard.view()),
+            (Layout::RowMajor, 2, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0])
+        );
+        let fortran =
+            Array2::from_shape_vec((2, 3).f(), vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]).unwrap();
+        assert_eq!(
+            layout(fortran.view()),
+            (Layout::ColumnMajor, 2, vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0])
+        );
+        assert_eq!(
+            layout(standard.slice(s![.., ..;2])),
+            (Layout::RowMajor, 2, vec![1.0, 3.0, 4.0, 6.0])
+        );
+    }
+}
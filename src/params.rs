@@ -0,0 +1,898 @@
+//! Typed training and dataset parameters.
+
+use libc::c_char;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryInto;
+use std::sync::OnceLock;
+
+use serde_json::{Map, Value};
+
+use lightgbm_sys;
+
+use crate::{Error, Result};
+
+/// Maps every LightGBM parameter name and alias to its canonical name.
+static PARAM_ALIASES: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+/// Load LightGBM's parameter names and aliases, once per process.
+fn param_aliases() -> Result<&'static HashMap<String, String>> {
+    if let Some(aliases) = PARAM_ALIASES.get() {
+        return Ok(aliases);
+    }
+
+    // get nessesary buffer size
+    let mut out_len = 0_i64;
+    lgbm_call!(lightgbm_sys::LGBM_DumpParamAliases(
+        0,
+        &mut out_len,
+        std::ptr::null_mut()
+    ))?;
+    let mut buffer = vec![
+        0u8;
+        out_len
+            .try_into()
+            .map_err(|_| Error::new("size negative"))?
+    ];
+    lgbm_call!(lightgbm_sys::LGBM_DumpParamAliases(
+        buffer.len() as i64,
+        &mut out_len,
+        buffer.as_mut_ptr() as *mut c_char
+    ))?;
+    // strip the final null character
+    buffer.truncate(buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len()));
+
+    // {"num_iterations": ["num_iteration", "n_iter", ...], ...}
+    let dump: HashMap<String, Vec<String>> = serde_json::from_slice(&buffer)
+        .map_err(|e| Error::new(format!("can't parse parameter aliases: {}", e)))?;
+    let mut aliases = HashMap::new();
+    for (name, name_aliases) in dump {
+        for alias in name_aliases {
+            aliases.insert(alias, name.clone());
+        }
+        aliases.insert(name.clone(), name);
+    }
+    Ok(PARAM_ALIASES.get_or_init(|| aliases))
+}
+
+/// Replace all parameter aliases by their canonical names, e.g. `n_estimators` by
+/// `num_iterations`.
+///
+/// Fails on unknown parameters, suggesting similar known names, and on parameters given
+/// more than once under different aliases.
+pub(crate) fn resolve_aliases(parameter: &Value) -> Result<Value> {
+    let object = parameter
+        .as_object()
+        .ok_or_else(|| Error::new("parameters have to be a JSON object"))?;
+    let aliases = param_aliases()?;
+
+    let mut resolved = Map::new();
+    let mut given_as: HashMap<&str, &str> = HashMap::new();
+    for (key, value) in object {
+        let name = aliases
+            .get(key)
+            .ok_or_else(|| unknown_parameter(key, aliases.keys()))?;
+        if let Some(other) = given_as.insert(name, key) {
+            return Err(Error::new(format!(
+                "parameters {} and {} are both aliases of {}, only set one of them",
+                other, key, name
+            )));
+        }
+        resolved.insert(name.clone(), value.clone());
+    }
+    Ok(Value::Object(resolved))
+}
+
+fn unknown_parameter<'a, I: Iterator<Item = &'a String>>(key: &str, known: I) -> Error {
+    let max_distance = std::cmp::max(2, key.len() / 3);
+    let mut suggestions = known
+        .map(|name| (edit_distance(key, name), name.as_str()))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect::<Vec<_>>();
+    suggestions.sort_unstable();
+    let suggestions = suggestions
+        .iter()
+        .take(3)
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>();
+
+    if suggestions.is_empty() {
+        Error::new(format!("unknown parameter {}", key))
+    } else {
+        Error::new(format!(
+            "unknown parameter {}, did you mean {}?",
+            key,
+            suggestions.join(", ")
+        ))
+    }
+}
+
+/// Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + if ca == *cb { 0 } else { 1 };
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Convert a JSON object of parameters to LightGBM's parameter string.
+///
+/// `{"objective": "binary", "metric": ["auc", "binary_logloss"], "verbose": -1}` becomes
+/// `objective=binary metric=auc,binary_logloss verbose=-1`. Strings are passed without quotes,
+/// arrays are comma separated and nested arrays are enclosed in brackets, as needed for
+/// `interaction_constraints`. Parameters set to `null` are left out.
+pub(crate) fn params_string(parameter: &Value) -> Result<String> {
+    let object = parameter
+        .as_object()
+        .ok_or_else(|| Error::new("parameters have to be a JSON object"))?;
+    let mut params = Vec::with_capacity(object.len());
+    for (key, value) in object {
+        if key.is_empty() || key.contains(|c: char| c == '=' || !is_param_char(c)) {
+            return Err(Error::new(format!("invalid parameter name '{}'", key)));
+        }
+        if value.is_null() {
+            continue;
+        }
+        let value = param_value(value, 0)
+            .map_err(|e| Error::new(format!("invalid value for parameter {}: {}", key, e)))?;
+        params.push(format!("{}={}", key, value));
+    }
+    Ok(params.join(" "))
+}
+
+/// LightGBM splits the parameter string at whitespace.
+fn is_param_char(c: char) -> bool {
+    !c.is_whitespace() && !c.is_control()
+}
+
+fn param_value(value: &Value, depth: usize) -> std::result::Result<String, String> {
+    match value {
+        Value::Null => Err(String::from("null is not allowed in lists")),
+        Value::Bool(b) => Ok(b.to_string()),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::String(s) if s.chars().all(is_param_char) => Ok(s.clone()),
+        Value::String(s) => Err(format!("'{}' contains whitespace", s)),
+        Value::Array(_) if depth > 1 => Err(String::from("lists are nested too deeply")),
+        Value::Array(values) => {
+            let values = values
+                .iter()
+                .map(|v| param_value(v, depth + 1))
+                .collect::<std::result::Result<Vec<_>, _>>()?
+                .join(",");
+            Ok(if depth == 0 {
+                values
+            } else {
+                format!("[{}]", values)
+            })
+        }
+        Value::Object(_) => Err(String::from("objects are not supported")),
+    }
+}
+
+/// Loss function to optimize, see LightGBM's `objective` parameter.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Objective {
+    Regression,
+    RegressionL1,
+    Huber,
+    Fair,
+    Poisson,
+    Quantile,
+    Mape,
+    Gamma,
+    Tweedie,
+    Binary,
+    Multiclass,
+    MulticlassOva,
+    CrossEntropy,
+    CrossEntropyLambda,
+    LambdaRank,
+    RankXendcg,
+    /// No built-in objective, used for custom objectives.
+    None,
+}
+
+impl Objective {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Objective::Regression => "regression",
+            Objective::RegressionL1 => "regression_l1",
+            Objective::Huber => "huber",
+            Objective::Fair => "fair",
+            Objective::Poisson => "poisson",
+            Objective::Quantile => "quantile",
+            Objective::Mape => "mape",
+            Objective::Gamma => "gamma",
+            Objective::Tweedie => "tweedie",
+            Objective::Binary => "binary",
+            Objective::Multiclass => "multiclass",
+            Objective::MulticlassOva => "multiclassova",
+            Objective::CrossEntropy => "cross_entropy",
+            Objective::CrossEntropyLambda => "cross_entropy_lambda",
+            Objective::LambdaRank => "lambdarank",
+            Objective::RankXendcg => "rank_xendcg",
+            Objective::None => "none",
+        }
+    }
+}
+
+/// Evaluation metric, see LightGBM's `metric` parameter.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Metric {
+    L1,
+    L2,
+    Rmse,
+    Quantile,
+    Mape,
+    Huber,
+    Fair,
+    Poisson,
+    Gamma,
+    GammaDeviance,
+    Tweedie,
+    /// NDCG at the positions given by `eval_at`.
+    Ndcg,
+    /// MAP at the positions given by `eval_at`.
+    Map,
+    Auc,
+    AveragePrecision,
+    BinaryLogloss,
+    BinaryError,
+    AucMu,
+    MultiLogloss,
+    MultiError,
+    CrossEntropy,
+    CrossEntropyLambda,
+    KullbackLeibler,
+    /// Disable all metrics, including the default one of the objective.
+    None,
+}
+
+impl Metric {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Metric::L1 => "l1",
+            Metric::L2 => "l2",
+            Metric::Rmse => "rmse",
+            Metric::Quantile => "quantile",
+            Metric::Mape => "mape",
+            Metric::Huber => "huber",
+            Metric::Fair => "fair",
+            Metric::Poisson => "poisson",
+            Metric::Gamma => "gamma",
+            Metric::GammaDeviance => "gamma_deviance",
+            Metric::Tweedie => "tweedie",
+            Metric::Ndcg => "ndcg",
+            Metric::Map => "map",
+            Metric::Auc => "auc",
+            Metric::AveragePrecision => "average_precision",
+            Metric::BinaryLogloss => "binary_logloss",
+            Metric::BinaryError => "binary_error",
+            Metric::AucMu => "auc_mu",
+            Metric::MultiLogloss => "multi_logloss",
+            Metric::MultiError => "multi_error",
+            Metric::CrossEntropy => "cross_entropy",
+            Metric::CrossEntropyLambda => "cross_entropy_lambda",
+            Metric::KullbackLeibler => "kullback_leibler",
+            Metric::None => "none",
+        }
+    }
+}
+
+/// Boosting algorithm, see LightGBM's `boosting` parameter.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BoostingType {
+    Gbdt,
+    RandomForest,
+    Dart,
+}
+
+impl BoostingType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BoostingType::Gbdt => "gbdt",
+            BoostingType::RandomForest => "rf",
+            BoostingType::Dart => "dart",
+        }
+    }
+}
+
+/// Sampling of the training data per iteration, see LightGBM's `data_sample_strategy`
+/// parameter.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DataSampleStrategy {
+    Bagging,
+    Goss,
+}
+
+impl DataSampleStrategy {
+    fn as_str(&self) -> &'static str {
+        match self {
+            DataSampleStrategy::Bagging => "bagging",
+            DataSampleStrategy::Goss => "goss",
+        }
+    }
+}
+
+/// Typed training parameters.
+///
+/// Only parameters which were set are passed to LightGBM, everything else keeps LightGBM's
+/// default. Parameters without a field can be set with [`TrainParamsBuilder::extra`].
+///
+/// Example
+/// ```
+/// use lightgbm::{Booster, Dataset, Metric, Objective, TrainParamsBuilder};
+///
+/// let dataset = Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train").unwrap();
+/// let params = TrainParamsBuilder::default()
+///     .objective(Objective::Binary)
+///     .metric(vec![Metric::Auc, Metric::BinaryLogloss])
+///     .num_iterations(10)
+///     .learning_rate(0.05)
+///     .monotone_constraints(vec![1; 28])
+///     .extra("max_bin", 63)
+///     .build()
+///     .unwrap();
+/// let bst = Booster::train(dataset, &params.to_value()).unwrap();
+/// ```
+#[derive(Builder, Clone, Debug, Default, PartialEq)]
+#[builder(default, setter(into, strip_option), build_fn(error = "Error"))]
+pub struct TrainParams {
+    pub objective: Option<Objective>,
+    pub boosting: Option<BoostingType>,
+    pub data_sample_strategy: Option<DataSampleStrategy>,
+    pub num_iterations: Option<i32>,
+    pub learning_rate: Option<f64>,
+    pub num_leaves: Option<i32>,
+    pub num_class: Option<i32>,
+    pub num_threads: Option<i32>,
+    pub deterministic: Option<bool>,
+    pub verbosity: Option<i32>,
+
+    // learning control
+    pub max_depth: Option<i32>,
+    pub min_data_in_leaf: Option<i32>,
+    pub min_sum_hessian_in_leaf: Option<f64>,
+    pub early_stopping_round: Option<i32>,
+    pub early_stopping_min_delta: Option<f64>,
+    pub first_metric_only: Option<bool>,
+    pub is_unbalance: Option<bool>,
+    pub scale_pos_weight: Option<f64>,
+
+    // regularisation
+    pub lambda_l1: Option<f64>,
+    pub lambda_l2: Option<f64>,
+    pub min_gain_to_split: Option<f64>,
+
+    // bagging and feature sampling
+    pub bagging_fraction: Option<f64>,
+    pub bagging_freq: Option<i32>,
+    pub feature_fraction: Option<f64>,
+    pub feature_fraction_bynode: Option<f64>,
+
+    // categorical features
+    pub max_cat_to_onehot: Option<i32>,
+    pub max_cat_threshold: Option<i32>,
+    pub cat_l2: Option<f64>,
+    pub cat_smooth: Option<f64>,
+    pub min_data_per_group: Option<i32>,
+
+    // constraints
+    /// One of -1, 0 or 1 per feature.
+    pub monotone_constraints: Option<Vec<i8>>,
+    /// Groups of feature indices which may interact.
+    pub interaction_constraints: Option<Vec<Vec<usize>>>,
+
+    // seeds
+    pub seed: Option<i32>,
+    pub bagging_seed: Option<i32>,
+    pub feature_fraction_seed: Option<i32>,
+    pub data_random_seed: Option<i32>,
+
+    // metrics
+    pub metric: Option<Vec<Metric>>,
+    /// Positions for `ndcg` and `map`.
+    pub eval_at: Option<Vec<usize>>,
+    pub is_provide_training_metric: Option<bool>,
+
+    /// Raw LightGBM parameters without a typed field.
+    #[builder(setter(custom))]
+    pub extra: BTreeMap<String, Value>,
+}
+
+impl TrainParamsBuilder {
+    /// Set a LightGBM parameter which has no typed field, e.g. `max_bin`.
+    pub fn extra<K: Into<String>, V: Into<Value>>(&mut self, key: K, value: V) -> &mut Self {
+        self.extra
+            .get_or_insert_with(BTreeMap::new)
+            .insert(key.into(), value.into());
+        self
+    }
+}
+
+impl TrainParams {
+    /// Convert to the JSON object accepted by [`crate::Booster::train`] and friends.
+    pub fn to_value(&self) -> Value {
+        let mut map = Map::new();
+        let mut insert = |key: &str, value: Option<Value>| {
+            if let Some(value) = value {
+                map.insert(key.to_string(), value);
+            }
+        };
+
+        insert("objective", self.objective.map(|o| o.as_str().into()));
+        insert("boosting", self.boosting.map(|b| b.as_str().into()));
+        insert(
+            "data_sample_strategy",
+            self.data_sample_strategy.map(|d| d.as_str().into()),
+        );
+        insert("num_iterations", self.num_iterations.map(Value::from));
+        insert("learning_rate", self.learning_rate.map(Value::from));
+        insert("num_leaves", self.num_leaves.map(Value::from));
+        insert("num_class", self.num_class.map(Value::from));
+        insert("num_threads", self.num_threads.map(Value::from));
+        insert("deterministic", self.deterministic.map(Value::from));
+        insert("verbosity", self.verbosity.map(Value::from));
+
+        insert("max_depth", self.max_depth.map(Value::from));
+        insert("min_data_in_leaf", self.min_data_in_leaf.map(Value::from));
+        insert(
+            "min_sum_hessian_in_leaf",
+            self.min_sum_hessian_in_leaf.map(Value::from),
+        );
+        insert(
+            "early_stopping_round",
+            self.early_stopping_round.map(Value::from),
+        );
+        insert(
+            "early_stopping_min_delta",
+            self.early_stopping_min_delta.map(Value::from),
+        );
+        insert("first_metric_only", self.first_metric_only.map(Value::from));
+        insert("is_unbalance", self.is_unbalance.map(Value::from));
+        insert("scale_pos_weight", self.scale_pos_weight.map(Value::from));
+
+        insert("lambda_l1", self.lambda_l1.map(Value::from));
+        insert("lambda_l2", self.lambda_l2.map(Value::from));
+        insert("min_gain_to_split", self.min_gain_to_split.map(Value::from));
+
+        insert("bagging_fraction", self.bagging_fraction.map(Value::from));
+        insert("bagging_freq", self.bagging_freq.map(Value::from));
+        insert("feature_fraction", self.feature_fraction.map(Value::from));
+        insert(
+            "feature_fraction_bynode",
+            self.feature_fraction_bynode.map(Value::from),
+        );
+
+        insert("max_cat_to_onehot", self.max_cat_to_onehot.map(Value::from));
+        insert("max_cat_threshold", self.max_cat_threshold.map(Value::from));
+        insert("cat_l2", self.cat_l2.map(Value::from));
+        insert("cat_smooth", self.cat_smooth.map(Value::from));
+        insert(
+            "min_data_per_group",
+            self.min_data_per_group.map(Value::from),
+        );
+
+        insert(
+            "monotone_constraints",
+            self.monotone_constraints.clone().map(Value::from),
+        );
+        insert(
+            "interaction_constraints",
+            self.interaction_constraints.clone().map(Value::from),
+        );
+
+        insert("seed", self.seed.map(Value::from));
+        insert("bagging_seed", self.bagging_seed.map(Value::from));
+        insert(
+            "feature_fraction_seed",
+            self.feature_fraction_seed.map(Value::from),
+        );
+        insert("data_random_seed", self.data_random_seed.map(Value::from));
+
+        insert(
+            "metric",
+            self.metric
+                .as_ref()
+                .map(|metrics| metrics.iter().map(|m| m.as_str()).collect()),
+        );
+        insert("eval_at", self.eval_at.clone().map(Value::from));
+        insert(
+            "is_provide_training_metric",
+            self.is_provide_training_metric.map(Value::from),
+        );
+
+        for (key, value) in &self.extra {
+            map.insert(key.clone(), value.clone());
+        }
+        Value::Object(map)
+    }
+}
+
+impl From<TrainParams> for Value {
+    fn from(params: TrainParams) -> Self {
+        params.to_value()
+    }
+}
+
+impl TrainParams {
+    /// Render LightGBM's parameter string, e.g. `objective=binary num_iterations=10`.
+    ///
+    /// Aliases among the `extra` parameters are replaced by their canonical names. Fails on
+    /// unknown `extra` parameters, on ones set twice under different aliases and on values
+    /// LightGBM can't represent, like an object.
+    pub fn to_param_string(&self) -> Result<String> {
+        params_string(&resolve_aliases(&self.to_value())?)
+    }
+}
+
+/// Parameters of dataset construction, like the binning of features and how files are read.
+///
+/// LightGBM only applies them when a [`crate::Dataset`] is created, see
+/// [`crate::Dataset::from_file_with_params`] and friends. Training parameters of a Dataset
+/// created with parameters are checked against them, see
+/// [`crate::Dataset::check_train_params`].
+///
+/// Example
+/// ```
+/// use lightgbm::{Dataset, DatasetBuilder};
+///
+/// let params = DatasetBuilder::default()
+///     .max_bin(63)
+///     .min_data_in_bin(5)
+///     .zero_as_missing(true)
+///     .build()
+///     .unwrap();
+/// let dataset = Dataset::from_file_with_params(
+///     "lightgbm-sys/lightgbm/examples/binary_classification/binary.train",
+///     &params,
+///     None,
+/// )
+/// .unwrap();
+/// ```
+#[derive(Builder, Clone, Debug, Default, PartialEq)]
+#[builder(
+    name = "DatasetBuilder",
+    default,
+    setter(into, strip_option),
+    build_fn(error = "Error")
+)]
+pub struct DatasetParams {
+    // binning
+    pub max_bin: Option<i32>,
+    /// One maximal number of bins per feature.
+    pub max_bin_by_feature: Option<Vec<i32>>,
+    pub min_data_in_bin: Option<i32>,
+    pub bin_construct_sample_cnt: Option<i32>,
+    pub data_random_seed: Option<i32>,
+    pub use_missing: Option<bool>,
+    pub zero_as_missing: Option<bool>,
+    pub feature_pre_filter: Option<bool>,
+    pub enable_bundle: Option<bool>,
+    pub forcedbins_filename: Option<String>,
+
+    // file loading
+    pub header: Option<bool>,
+    /// Index of the label column, or its name prefixed by `name:`.
+    pub label_column: Option<String>,
+    pub weight_column: Option<String>,
+    pub group_column: Option<String>,
+    /// Comma separated indices of columns to ignore, or names prefixed by `name:`.
+    pub ignore_column: Option<String>,
+    pub two_round: Option<bool>,
+    pub precise_float_parser: Option<bool>,
+
+    /// Raw LightGBM parameters without a typed field.
+    #[builder(setter(custom))]
+    pub extra: BTreeMap<String, Value>,
+}
+
+impl DatasetBuilder {
+    /// Set a LightGBM dataset parameter which has no typed field, e.g. `max_cat_to_onehot`.
+    pub fn extra<K: Into<String>, V: Into<Value>>(&mut self, key: K, value: V) -> &mut Self {
+        self.extra
+            .get_or_insert_with(BTreeMap::new)
+            .insert(key.into(), value.into());
+        self
+    }
+}
+
+impl DatasetParams {
+    /// Convert to a JSON object like the training parameters.
+    pub fn to_value(&self) -> Value {
+        let mut map = Map::new();
+        let mut insert = |key: &str, value: Option<Value>| {
+            if let Some(value) = value {
+                map.insert(key.to_string(), value);
+            }
+        };
+
+        insert("max_bin", self.max_bin.map(Value::from));
+        insert(
+            "max_bin_by_feature",
+            self.max_bin_by_feature.clone().map(Value::from),
+        );
+        insert("min_data_in_bin", self.min_data_in_bin.map(Value::from));
+        insert(
+            "bin_construct_sample_cnt",
+            self.bin_construct_sample_cnt.map(Value::from),
+        );
+        insert("data_random_seed", self.data_random_seed.map(Value::from));
+        insert("use_missing", self.use_missing.map(Value::from));
+        insert("zero_as_missing", self.zero_as_missing.map(Value::from));
+        insert(
+            "feature_pre_filter",
+            self.feature_pre_filter.map(Value::from),
+        );
+        insert("enable_bundle", self.enable_bundle.map(Value::from));
+        insert(
+            "forcedbins_filename",
+            self.forcedbins_filename.clone().map(Value::from),
+        );
+
+        insert("header", self.header.map(Value::from));
+        insert("label_column", self.label_column.clone().map(Value::from));
+        insert("weight_column", self.weight_column.clone().map(Value::from));
+        insert("group_column", self.group_column.clone().map(Value::from));
+        insert("ignore_column", self.ignore_column.clone().map(Value::from));
+        insert("two_round", self.two_round.map(Value::from));
+        insert(
+            "precise_float_parser",
+            self.precise_float_parser.map(Value::from),
+        );
+
+        for (key, value) in &self.extra {
+            map.insert(key.clone(), value.clone());
+        }
+        Value::Object(map)
+    }
+
+    /// Render LightGBM's parameter string, e.g. `max_bin=63 header=true`.
+    pub fn to_param_string(&self) -> Result<String> {
+        params_string(&resolve_aliases(&self.to_value())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("num_leaves", "num_leaves"), 0);
+        assert_eq!(edit_distance("num_leaf", "num_leaves"), 3);
+        assert_eq!(edit_distance("lerning_rate", "learning_rate"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn resolve_param_aliases() {
+        let params = json! {{
+            "objective": "binary",
+            "n_estimators": 5,
+            "early_stopping_rounds": 2,
+            "sub_row": 0.5
+        }};
+        assert_eq!(
+            resolve_aliases(&params),
+            Ok(json! {{
+                "objective": "binary",
+                "num_iterations": 5,
+                "early_stopping_round": 2,
+                "bagging_fraction": 0.5
+            }})
+        );
+    }
+
+    #[test]
+    fn dataset_params() {
+        let params = DatasetBuilder::default()
+            .max_bin(63)
+            .header(true)
+            .label_column("name:target")
+            .extra("max_cat_to_onehot", 8)
+            .build()
+            .unwrap();
+        assert_eq!(
+            params.to_value(),
+            json! {{
+                "max_bin": 63,
+                "header": true,
+                "label_column": "name:target",
+                "max_cat_to_onehot": 8
+            }}
+        );
+        assert_eq!(
+            DatasetParams::default().to_param_string(),
+            Ok(String::new())
+        );
+    }
+
+    #[test]
+    fn conflicting_aliases() {
+        let params = json! {{"num_iterations": 10, "n_estimators": 5}};
+        assert!(resolve_aliases(&params).is_err());
+    }
+
+    #[test]
+    fn unknown_params() {
+        let err = resolve_aliases(&json! {{"num_leavs": 10}}).unwrap_err();
+        assert!(err.to_string().contains("did you mean"));
+        assert!(err.to_string().contains("num_leaves"));
+
+        let err = resolve_aliases(&json! {{"completely_unknown_parameter": 1}}).unwrap_err();
+        assert!(!err.to_string().contains("did you mean"));
+    }
+
+    #[test]
+    fn scalar_values() {
+        let params = json! {{
+            "objective": "binary",
+            "num_leaves": 31,
+            "learning_rate": 0.05,
+            "lambda_l1": 1e-5,
+            "verbosity": -1,
+            "is_unbalance": true,
+            "linear_tree": false,
+            "seed": null
+        }};
+        let rendered = params_string(&params).unwrap();
+        let mut rendered = rendered.split(' ').collect::<Vec<_>>();
+        rendered.sort_unstable();
+        assert_eq!(
+            rendered,
+            vec![
+                "is_unbalance=true",
+                "lambda_l1=1e-5",
+                "learning_rate=0.05",
+                "linear_tree=false",
+                "num_leaves=31",
+                "objective=binary",
+                "verbosity=-1",
+            ]
+        );
+    }
+
+    #[test]
+    fn list_values() {
+        assert_eq!(
+            params_string(&json! {{"metric": ["auc", "binary_logloss"]}}),
+            Ok(String::from("metric=auc,binary_logloss"))
+        );
+        assert_eq!(
+            params_string(&json! {{"monotone_constraints": [1, -1, 0]}}),
+            Ok(String::from("monotone_constraints=1,-1,0"))
+        );
+        assert_eq!(
+            params_string(&json! {{"feature_contri": [1.0, 0.5]}}),
+            Ok(String::from("feature_contri=1.0,0.5"))
+        );
+        assert_eq!(
+            params_string(&json! {{"interaction_constraints": [[0, 1, 2], [2, 3]]}}),
+            Ok(String::from("interaction_constraints=[0,1,2],[2,3]"))
+        );
+        assert_eq!(
+            params_string(&json! {{"metric": "auc,binary_logloss"}}),
+            Ok(String::from("metric=auc,binary_logloss"))
+        );
+    }
+
+    #[test]
+    fn invalid_values() {
+        assert!(params_string(&json! {[1, 2]}).is_err());
+        assert!(params_string(&json! {{"objective": {"name": "binary"}}}).is_err());
+        assert!(params_string(&json! {{"metric": [{"name": "auc"}]}}).is_err());
+        assert!(params_string(&json! {{"interaction_constraints": [[[0]]]}}).is_err());
+        assert!(params_string(&json! {{"metric": ["auc", null]}}).is_err());
+        assert!(params_string(&json! {{"objective": "binary num_leaves=2"}}).is_err());
+        assert!(params_string(&json! {{"num leaves": 2}}).is_err());
+        assert!(params_string(&json! {{"num_leaves=2": 2}}).is_err());
+    }
+
+    #[test]
+    fn only_set_params() {
+        let params = TrainParamsBuilder::default().build().unwrap();
+        assert_eq!(params.to_value(), json! {{}});
+
+        let params = TrainParamsBuilder::default()
+            .objective(Objective::Binary)
+            .num_iterations(10)
+            .learning_rate(0.1)
+            .first_metric_only(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            params.to_value(),
+            json! {{
+                "objective": "binary",
+                "num_iterations": 10,
+                "learning_rate": 0.1,
+                "first_metric_only": true
+            }}
+        );
+    }
+
+    #[test]
+    fn list_params() {
+        let params = TrainParamsBuilder::default()
+            .metric(vec![Metric::Ndcg, Metric::Map])
+            .eval_at(vec![1, 3, 5])
+            .monotone_constraints(vec![1, -1, 0])
+            .interaction_constraints(vec![vec![0, 1], vec![2]])
+            .build()
+            .unwrap();
+        assert_eq!(
+            params.to_value(),
+            json! {{
+                "metric": ["ndcg", "map"],
+                "eval_at": [1, 3, 5],
+                "monotone_constraints": [1, -1, 0],
+                "interaction_constraints": [[0, 1], [2]]
+            }}
+        );
+        let rendered = params.to_param_string().unwrap();
+        let mut rendered = rendered.split(' ').collect::<Vec<_>>();
+        rendered.sort_unstable();
+        assert_eq!(
+            rendered,
+            vec![
+                "eval_at=1,3,5",
+                "interaction_constraints=[0,1],[2]",
+                "metric=ndcg,map",
+                "monotone_constraints=1,-1,0",
+            ]
+        );
+    }
+
+    #[test]
+    fn extra_params() {
+        let params = TrainParamsBuilder::default()
+            .num_leaves(7)
+            .extra("max_bin", 63)
+            .extra("linear_tree", true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            params.to_value(),
+            json! {{"num_leaves": 7, "max_bin": 63, "linear_tree": true}}
+        );
+
+        let params = TrainParamsBuilder::default()
+            .extra("forcedsplits", json! {{"feature": 0}})
+            .build()
+            .unwrap();
+        assert!(params.to_param_string().is_err());
+
+        let params = TrainParamsBuilder::default()
+            .extra("sub_row", 0.5)
+            .build()
+            .unwrap();
+        assert_eq!(
+            params.to_param_string(),
+            Ok(String::from("bagging_fraction=0.5"))
+        );
+    }
+
+    #[test]
+    fn extra_params_are_validated() {
+        let params = TrainParamsBuilder::default()
+            .num_iterations(10)
+            .extra("n_estimators", 5)
+            .build()
+            .unwrap();
+        assert!(params.to_param_string().is_err());
+
+        let params = TrainParamsBuilder::default()
+            .extra("num_leavs", 7)
+            .build()
+            .unwrap();
+        assert!(params.to_param_string().is_err());
+    }
+}
@@ -0,0 +1,334 @@
+//! Prediction types and their outputs.
+
+use lightgbm_sys;
+
+use crate::{Error, Result};
+
+/// What to predict, see LightGBM's `predict_raw_score`, `predict_leaf_index` and
+/// `predict_contrib` parameters.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PredictType {
+    /// Predictions transformed by the objective, e.g. probabilities for `binary`.
+    Normal,
+    /// Untransformed scores.
+    RawScore,
+    /// Index of the leaf every row falls into, per tree.
+    LeafIndex,
+    /// SHAP feature contributions, plus the expected value of the model.
+    Contrib,
+}
+
+impl PredictType {
+    pub(crate) fn as_c_int(&self) -> i32 {
+        let predict_type = match self {
+            PredictType::Normal => lightgbm_sys::C_API_PREDICT_NORMAL,
+            PredictType::RawScore => lightgbm_sys::C_API_PREDICT_RAW_SCORE,
+            PredictType::LeafIndex => lightgbm_sys::C_API_PREDICT_LEAF_INDEX,
+            PredictType::Contrib => lightgbm_sys::C_API_PREDICT_CONTRIB,
+        };
+        predict_type as i32
+    }
+}
+
+/// Range of iterations, i.e. trees, used to predict or to save a model.
+///
+/// By default all iterations are used, up to the best iteration if the Booster was trained
+/// with early stopping.
+///
+/// Example
+/// ```
+/// use lightgbm::IterationRange;
+///
+/// // the first 10 iterations
+/// let first = IterationRange::new(0, Some(10));
+/// // all iterations from the 10th on
+/// let rest = IterationRange::new(10, None);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct IterationRange {
+    start_iteration: usize,
+    num_iteration: Option<usize>,
+}
+
+impl IterationRange {
+    /// `num_iteration` iterations starting at the 0-based `start_iteration`, `None` or `0`
+    /// meaning all remaining ones.
+    pub fn new(start_iteration: usize, num_iteration: Option<usize>) -> Self {
+        Self {
+            start_iteration,
+            num_iteration,
+        }
+    }
+
+    /// The `start_iteration` and `num_iteration` arguments of the C API.
+    pub(crate) fn c_args(&self, best_iteration: Option<i32>) -> (i32, i32) {
+        let num_iteration = match self.num_iteration {
+            Some(num_iteration) => num_iteration as i32,
+            // like the python package, the best iteration only applies to ranges from the start
+            None if self.start_iteration == 0 => best_iteration.unwrap_or(-1),
+            None => -1,
+        };
+        (self.start_iteration as i32, num_iteration)
+    }
+}
+
+/// Predictions for several rows, stored row by row in a single buffer.
+///
+/// Every row is a `shape().1 × shape().2` matrix, depending on the [`PredictType`]:
+///
+/// | `PredictType`           | shape                              |
+/// |-------------------------|------------------------------------|
+/// | `Normal`, `RawScore`    | rows × classes × 1                 |
+/// | `LeafIndex`             | rows × iterations × classes        |
+/// | `Contrib`               | rows × classes × (features + 1)    |
+///
+/// The last contribution of every class is the expected value of the model, so the
+/// contributions of a row and class sum up to its raw score.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Predictions {
+    values: Vec<f64>,
+    shape: (usize, usize, usize),
+}
+
+impl Predictions {
+    /// Wrap the output of LightGBM for `num_rows` rows of `predict_type`.
+    pub(crate) fn new(
+        values: Vec<f64>,
+        num_rows: usize,
+        num_class: usize,
+        row_len: usize,
+        predict_type: PredictType,
+    ) -> Self {
+        let shape = match predict_type {
+            PredictType::Normal | PredictType::RawScore => (num_rows, num_class, 1),
+            PredictType::LeafIndex => (num_rows, row_len / num_class, num_class),
+            PredictType::Contrib => (num_rows, num_class, row_len / num_class),
+        };
+        Self { values, shape }
+    }
+
+    /// Number of rows and the dimensions of the predictions of every row.
+    pub fn shape(&self) -> (usize, usize, usize) {
+        self.shape
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.shape.0
+    }
+
+    /// The predictions of row `row`, e.g. its contributions class by class.
+    pub fn row(&self, row: usize) -> &[f64] {
+        let row_len = self.shape.1 * self.shape.2;
+        &self.values[row * row_len..(row + 1) * row_len]
+    }
+
+    /// Iterate over the predictions of all rows.
+    pub fn rows(&self) -> impl Iterator<Item = &[f64]> {
+        (0..self.num_rows()).map(move |row| self.row(row))
+    }
+
+    /// The value at `(row, i, j)` of [`Predictions::shape`], e.g. the contribution of feature
+    /// `j` to class `i` of row `row`.
+    pub fn get(&self, row: usize, i: usize, j: usize) -> f64 {
+        assert!(i < self.shape.1 && j < self.shape.2, "index out of bounds");
+        self.row(row)[i * self.shape.2 + j]
+    }
+
+    /// All predictions as a flat slice, row by row.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    pub fn into_values(self) -> Vec<f64> {
+        self.values
+    }
+}
+
+/// Storage order of a [`SparseMatrix`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SparseFormat {
+    /// Compressed sparse rows, `indptr` has one entry per row plus one.
+    Csr,
+    /// Compressed sparse columns, `indptr` has one entry per column plus one.
+    Csc,
+}
+
+/// Owned sparse matrix in CSR or CSC format, as returned by
+/// [`crate::Booster::predict_contrib_csr`] and [`crate::Booster::predict_contrib_csc`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseMatrix<T> {
+    format: SparseFormat,
+    shape: (usize, usize),
+    indptr: Vec<i64>,
+    indices: Vec<i32>,
+    values: Vec<T>,
+}
+
+impl<T: Copy> SparseMatrix<T> {
+    pub(crate) fn new(
+        format: SparseFormat,
+        shape: (usize, usize),
+        indptr: Vec<i64>,
+        indices: Vec<i32>,
+        values: Vec<T>,
+    ) -> Self {
+        Self {
+            format,
+            shape,
+            indptr,
+            indices,
+            values,
+        }
+    }
+
+    pub fn format(&self) -> SparseFormat {
+        self.format
+    }
+
+    /// Number of rows and columns.
+    pub fn shape(&self) -> (usize, usize) {
+        self.shape
+    }
+
+    /// Offsets of the rows (CSR) or columns (CSC) into `indices` and `values`.
+    pub fn indptr(&self) -> &[i64] {
+        &self.indptr
+    }
+
+    /// Column (CSR) or row (CSC) index of every stored value.
+    pub fn indices(&self) -> &[i32] {
+        &self.indices
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// The value at `(row, col)`, `None` if it is not stored, i.e. zero.
+    pub fn get(&self, row: usize, col: usize) -> Option<T> {
+        assert!(
+            row < self.shape.0 && col < self.shape.1,
+            "index out of bounds"
+        );
+        let (outer, inner) = match self.format {
+            SparseFormat::Csr => (row, col),
+            SparseFormat::Csc => (col, row),
+        };
+        let range = self.indptr[outer] as usize..self.indptr[outer + 1] as usize;
+        self.indices[range.clone()]
+            .iter()
+            .position(|index| *index as usize == inner)
+            .map(|pos| self.values[range.start + pos])
+    }
+
+    /// Decompose into `(indptr, indices, values)`.
+    pub fn into_parts(self) -> (Vec<i64>, Vec<i32>, Vec<T>) {
+        (self.indptr, self.indices, self.values)
+    }
+}
+
+/// Order the rows of every query by descending score, e.g. the predictions of a `lambdarank`
+/// model. `group_sizes` are the numbers of contiguous rows of the queries, like for
+/// [`crate::Dataset::set_groups`], and the returned orderings hold the indices of the rows
+/// within their query. Rows with equal scores keep their order.
+///
+/// Example
+/// ```
+/// use lightgbm::rank_groups;
+///
+/// let scores = [0.1, 0.9, 0.5, -1.0, 2.0];
+/// let rankings = rank_groups(&scores, &[3, 2]).unwrap();
+/// assert_eq!(rankings, vec![vec![1, 2, 0], vec![1, 0]]);
+/// ```
+pub fn rank_groups(scores: &[f64], group_sizes: &[i32]) -> Result<Vec<Vec<usize>>> {
+    if group_sizes.iter().any(|size| *size <= 0) {
+        return Err(Error::new("group sizes have to be positive"));
+    }
+    let num_rows: usize = group_sizes.iter().map(|size| *size as usize).sum();
+    if num_rows != scores.len() {
+        return Err(Error::new(format!(
+            "groups contain {} rows, but got {} scores",
+            num_rows,
+            scores.len()
+        )));
+    }
+    let mut start = 0;
+    let rankings = group_sizes
+        .iter()
+        .map(|size| {
+            let group = &scores[start..start + *size as usize];
+            start += *size as usize;
+            let mut order = (0..group.len()).collect::<Vec<_>>();
+            order.sort_by(|a, b| group[*b].total_cmp(&group[*a]));
+            order
+        })
+        .collect();
+    Ok(rankings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparse_matrix_get() {
+        // [[1.0, 0.0, 2.0],
+        //  [0.0, 0.0, 3.0]]
+        let csr = SparseMatrix::new(
+            SparseFormat::Csr,
+            (2, 3),
+            vec![0, 2, 3],
+            vec![0, 2, 2],
+            vec![1.0, 2.0, 3.0],
+        );
+        let csc = SparseMatrix::new(
+            SparseFormat::Csc,
+            (2, 3),
+            vec![0, 1, 1, 3],
+            vec![0, 0, 1],
+            vec![1.0, 2.0, 3.0],
+        );
+        for matrix in &[csr, csc] {
+            assert_eq!(matrix.get(0, 0), Some(1.0));
+            assert_eq!(matrix.get(0, 1), None);
+            assert_eq!(matrix.get(0, 2), Some(2.0));
+            assert_eq!(matrix.get(1, 2), Some(3.0));
+        }
+    }
+
+    #[test]
+    fn iteration_range() {
+        assert_eq!(IterationRange::default().c_args(None), (0, -1));
+        assert_eq!(IterationRange::default().c_args(Some(7)), (0, 7));
+        assert_eq!(IterationRange::new(0, Some(3)).c_args(Some(7)), (0, 3));
+        assert_eq!(IterationRange::new(5, None).c_args(Some(7)), (5, -1));
+    }
+
+    #[test]
+    fn contrib_shape() {
+        let values = (0..12).map(f64::from).collect();
+        let predictions = Predictions::new(values, 2, 2, 6, PredictType::Contrib);
+        assert_eq!(predictions.shape(), (2, 2, 3));
+        assert_eq!(predictions.row(1), &[6.0, 7.0, 8.0, 9.0, 10.0, 11.0]);
+        assert_eq!(predictions.get(1, 1, 0), 9.0);
+        assert_eq!(predictions.rows().count(), 2);
+    }
+
+    #[test]
+    fn rank_within_groups() {
+        let scores = [0.3, 0.3, 0.7, 1.5, -0.5, 0.0];
+        assert_eq!(
+            rank_groups(&scores, &[3, 1, 2]).unwrap(),
+            vec![vec![2, 0, 1], vec![0], vec![1, 0]]
+        );
+        assert!(rank_groups(&scores, &[3, 2]).is_err());
+        assert!(rank_groups(&scores, &[3, 0, 3]).is_err());
+    }
+
+    #[test]
+    fn leaf_index_shape() {
+        let values = vec![0.0; 12];
+        let predictions = Predictions::new(values, 2, 3, 6, PredictType::LeafIndex);
+        assert_eq!(predictions.shape(), (2, 2, 3));
+    }
+}
@@ -0,0 +1,38 @@
+//! Fixtures shared by the unit tests.
+
+use serde_json::{json, Value};
+
+use crate::{Booster, Dataset, Result};
+
+pub(crate) fn read_train_file() -> Result<Dataset> {
+    Dataset::from_file(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.train")
+}
+
+pub(crate) fn read_valid_file(train: &Dataset) -> Result<Dataset> {
+    Dataset::from_file_with_reference(
+        "lightgbm-sys/lightgbm/examples/binary_classification/binary.test",
+        train,
+    )
+}
+
+pub(crate) fn train_booster(params: &Value) -> Booster {
+    let dataset = read_train_file().unwrap();
+    Booster::train(dataset, params).unwrap()
+}
+
+pub(crate) fn train_booster_with_iterations(num_iterations: i64) -> Booster {
+    let mut params = default_params();
+    params["num_iterations"] = json!(num_iterations);
+    train_booster(&params)
+}
+
+pub(crate) fn default_params() -> Value {
+    json! {
+        {
+            "num_iterations": 1,
+            "objective": "binary",
+            "metric": "auc",
+            "data_random_seed": 0
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! Extensions of the training loop written in Rust.
+
+use crate::{Booster, Dataset, Result};
+
+pub(crate) type ObjectiveFn<'a> =
+    dyn FnMut(&[f64], &[f32], Option<&[f32]>) -> (Vec<f32>, Vec<f32>) + 'a;
+
+type MetricFn<'a> = dyn Fn(&[f64], &Dataset) -> f64 + 'a;
+
+/// User-defined evaluation metric.
+///
+/// Evaluated after every iteration on the training Dataset and all validation Datasets, next
+/// to the built-in `metric`s, and can drive early stopping just like those.
+///
+/// Example
+/// ```
+/// use lightgbm::CustomMetric;
+///
+/// // share of positive predictions
+/// let metric = CustomMetric::new("positive_rate", false, |preds, _dataset| {
+///     preds.iter().filter(|p| **p > 0.5).count() as f64 / preds.len() as f64
+/// });
+/// ```
+pub struct CustomMetric<'a> {
+    pub(crate) name: String,
+    pub(crate) higher_is_better: bool,
+    pub(crate) func: Box<MetricFn<'a>>,
+}
+
+impl<'a> CustomMetric<'a> {
+    /// Create a new metric called `name`.
+    ///
+    /// `func` receives the current predictions and the Dataset they were made for. The
+    /// predictions are transformed by the objective (e.g. probabilities for `binary`), except
+    /// for custom objectives where they are raw scores. For multiclass models they are laid
+    /// out class by class, i.e. index `class * num_data + row`.
+    pub fn new<F>(name: &str, higher_is_better: bool, func: F) -> Self
+    where
+        F: Fn(&[f64], &Dataset) -> f64 + 'a,
+    {
+        Self {
+            name: name.to_string(),
+            higher_is_better,
+            func: Box::new(func),
+        }
+    }
+}
+
+/// Value of one metric on one Dataset after a training iteration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EvalResult {
+    /// `training` or the name of the validation Dataset.
+    pub dataset_name: String,
+    pub metric_name: String,
+    pub value: f64,
+    pub higher_is_better: bool,
+}
+
+impl EvalResult {
+    pub(crate) fn new(
+        dataset_name: &str,
+        metric_name: &str,
+        value: f64,
+        higher_is_better: bool,
+    ) -> Self {
+        Self {
+            dataset_name: dataset_name.to_string(),
+            metric_name: metric_name.to_string(),
+            value,
+            higher_is_better,
+        }
+    }
+
+    pub(crate) fn is_training(&self) -> bool {
+        self.dataset_name == "training"
+    }
+}
+
+/// Whether training should go on after a [`Callback`] was invoked.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CallbackAction {
+    Continue,
+    Stop,
+}
+
+/// Hooks into the training loop, e.g. for logging, progress bars, parameter schedules or
+/// checkpointing.
+///
+/// Both methods get mutable access to the Booster being trained, so callbacks can change
+/// parameters with [`Booster::reset_parameter`] or save the model.
+///
+/// Example
+/// ```
+/// extern crate serde_json;
+/// use lightgbm::{Booster, Callback, CallbackAction, EvalResult, Result};
+/// use serde_json::json;
+///
+/// /// Decays the learning rate by 1% per iteration.
+/// struct LearningRateDecay(f64);
+///
+/// impl Callback for LearningRateDecay {
+///     fn before_iteration(&mut self, booster: &mut Booster, iteration: usize) -> Result<()> {
+///         let learning_rate = self.0 * 0.99_f64.powi(iteration as i32);
+///         booster.reset_parameter(&json! {{"learning_rate": learning_rate}})
+///     }
+///
+///     fn after_iteration(
+///         &mut self,
+///         _booster: &mut Booster,
+///         iteration: usize,
+///         results: &[EvalResult],
+///     ) -> Result<CallbackAction> {
+///         for result in results {
+///             println!("[{}] {} {}: {}", iteration, result.dataset_name, result.metric_name, result.value);
+///         }
+///         Ok(CallbackAction::Continue)
+///     }
+/// }
+/// ```
+pub trait Callback {
+    /// Called before `iteration` (0-based) is trained.
+    fn before_iteration(&mut self, _booster: &mut Booster, _iteration: usize) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called after `iteration` (0-based) was trained and evaluated. Training stops if any
+    /// callback returns [`CallbackAction::Stop`].
+    fn after_iteration(
+        &mut self,
+        _booster: &mut Booster,
+        _iteration: usize,
+        _results: &[EvalResult],
+    ) -> Result<CallbackAction> {
+        Ok(CallbackAction::Continue)
+    }
+}
+
+impl<C: Callback + ?Sized> Callback for &mut C {
+    fn before_iteration(&mut self, booster: &mut Booster, iteration: usize) -> Result<()> {
+        (**self).before_iteration(booster, iteration)
+    }
+
+    fn after_iteration(
+        &mut self,
+        booster: &mut Booster,
+        iteration: usize,
+        results: &[EvalResult],
+    ) -> Result<CallbackAction> {
+        (**self).after_iteration(booster, iteration, results)
+    }
+}
+
+/// Optional parts of training: validation data, custom objective, custom metrics and
+/// callbacks.
+///
+/// Example
+/// ```
+/// use lightgbm::{CustomMetric, Dataset, TrainOptions};
+///
+/// let train = Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train").unwrap();
+/// let valid = Dataset::from_file_with_reference("lightgbm-sys/lightgbm/examples/binary_classification/binary.test", &train).unwrap();
+/// let options = TrainOptions::new()
+///     .valid_set("valid", valid)
+///     .metric(CustomMetric::new("mean_prediction", false, |preds, _dataset| {
+///         preds.iter().sum::<f64>() / preds.len() as f64
+///     }));
+/// ```
+#[derive(Default)]
+pub struct TrainOptions<'a> {
+    pub(crate) valid_sets: Vec<(String, Dataset)>,
+    pub(crate) objective: Option<Box<ObjectiveFn<'a>>>,
+    pub(crate) metrics: Vec<CustomMetric<'a>>,
+    pub(crate) callbacks: Vec<Box<dyn Callback + 'a>>,
+    pub(crate) early_stopping_metric: Option<String>,
+}
+
+impl<'a> TrainOptions<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a named validation Dataset, which has to be created with the training Dataset as
+    /// reference.
+    pub fn valid_set(mut self, name: &str, dataset: Dataset) -> Self {
+        self.valid_sets.push((name.to_string(), dataset));
+        self
+    }
+
+    /// Train with a custom objective, see [`crate::Booster::train_with_objective`].
+    pub fn objective<F>(mut self, objective: F) -> Self
+    where
+        F: FnMut(&[f64], &[f32], Option<&[f32]>) -> (Vec<f32>, Vec<f32>) + 'a,
+    {
+        self.objective = Some(Box::new(objective));
+        self
+    }
+
+    /// Add a custom evaluation metric.
+    pub fn metric(mut self, metric: CustomMetric<'a>) -> Self {
+        self.metrics.push(metric);
+        self
+    }
+
+    /// Watch only the metric `name`, built-in or custom, for early stopping. Without it all
+    /// metrics are watched, or only the first one in the order of the `metric` parameter if
+    /// `first_metric_only` is set.
+    pub fn early_stopping_metric(mut self, name: &str) -> Self {
+        self.early_stopping_metric = Some(name.to_string());
+        self
+    }
+
+    /// Add a callback, invoked in the order they were added. Pass `&mut callback` to inspect
+    /// its state after training.
+    pub fn callback<C: Callback + 'a>(mut self, callback: C) -> Self {
+        self.callbacks.push(Box::new(callback));
+        self
+    }
+}